@@ -0,0 +1,70 @@
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use toml::{Table, Value};
+
+/// The file `aoc verify` reads when no other is given.
+pub const DEFAULT_FILE: &str = "answers.toml";
+
+/// Known-good answers, keyed by day and part:
+///
+/// ```toml
+/// [day01]
+/// part1 = 24000
+/// part2 = "45000"
+/// ```
+///
+/// Answers may be integers or strings; multi-line answers like day 10's
+/// picture go in a `'''` string.
+pub struct AnswerKey(Table);
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Missing,
+}
+
+impl AnswerKey {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+        text.parse().map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    pub fn expected(&self, day: u32, part: u32) -> Option<String> {
+        let value = self.0.get(&format!("day{day:02}"))?.get(format!("part{part}"))?;
+        match value {
+            Value::String(s) => Some(s.trim_end_matches('\n').to_string()),
+            other => Some(other.to_string()),
+        }
+    }
+
+    pub fn check(&self, day: u32, part: u32, answer: &str) -> Verdict {
+        match self.expected(day, part) {
+            None => Verdict::Missing,
+            Some(expected) if expected == answer.trim_end_matches('\n') => Verdict::Pass,
+            Some(expected) => Verdict::Fail { expected },
+        }
+    }
+}
+
+impl FromStr for AnswerKey {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<Table>().map(AnswerKey).map_err(|e| e.message().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answer_key_test() {
+        let key: AnswerKey = "[day01]\npart1 = 24000\n\n[day10]\npart2 = '''\n#.\n.#\n'''\n".parse().unwrap();
+        assert_eq!(key.check(1, 1, "24000"), Verdict::Pass);
+        assert_eq!(key.check(1, 1, "24001"), Verdict::Fail { expected: "24000".into() });
+        assert_eq!(key.check(1, 2, "45000"), Verdict::Missing);
+        assert_eq!(key.check(10, 2, "#.\n.#"), Verdict::Pass);
+    }
+}
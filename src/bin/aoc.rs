@@ -0,0 +1,889 @@
+use std::io::{self, IsTerminal};
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+use itertools::Itertools;
+use advent2022::{answers, days, input, Answers, ParseError, Runner, Solution, Timings};
+use advent2022::answers::{AnswerKey, Verdict};
+use advent2022::days::day01::{self, Day01};
+use advent2022::days::day02;
+use advent2022::days::day03::{self, Day03};
+use advent2022::days::day04::{Day04, SectionIndex};
+use advent2022::days::day05::{CrateMover9000, CrateMover9001, CrateYard, Crane, Day05};
+use advent2022::days::day06;
+use advent2022::days::day07::{self, Day07, DirTree};
+use advent2022::rng::Rng;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+const USAGE: &str = "\
+usage: aoc run <days> [--time] [--format text|json] [input options]
+       aoc bench <days> [-n N] [input options]
+       aoc verify <days> [--answers FILE] [input options]
+       aoc gen <day> [--size N] [--seed S]
+       aoc check [<days>] [-n N] [--seed S]
+       aoc elves [--top N] [--stats | --stream] [input options]
+       aoc rps [--rules FILE] [--analyze] [input options]
+       aoc rucksacks [--group K] [input options]
+       aoc sections [input options]
+       aoc overlaps <range>... [input options]
+       aoc crane [--model 9000|9001] [--trace] [--step K] [input options]
+       aoc markers [--size N]... [input options]
+       aoc du [--depth N] [input options]
+       aoc find [--name GLOB] [--min N] [--max N] [--type f|d] [input options]
+       aoc largest [--top N] [input options]
+       aoc free <bytes> [--disk N] [input options]
+       aoc tree [--format text|json|sh] [input options]
+
+<days> is a day number (7), an inclusive range (3..9) or `all`.
+
+`run` prints the answers, and with --time how long parsing and each part took.
+With --format json it prints one JSON object per day instead, timings included.
+`bench` runs each day N times (default 10) and prints tab-separated
+min/median/max nanoseconds for every stage.
+`verify` checks each answer against FILE (default: answers.toml), printing
+PASS, FAIL or MISSING, and exits nonzero if any answer is wrong.
+`gen` writes a random input for one day to stdout. The same seed (default 0)
+always gives the same input; the size defaults to roughly the real puzzle's.
+`check` runs the days that have a brute-force reference (15, 16, 17 and 19,
+or those of <days>) against it on N small random inputs (default 100), seeded
+S, S+1, ... It prints the input of the first disagreement and exits nonzero.
+`elves` lists the N elves (default 3) carrying the most calories in day 1's
+input, with their items. --stats prints the mean, median and a histogram of
+the elves' totals instead, or as well if --top is also given. --stream reads
+the input a line at a time and lists only the totals, for inventories too big
+to load.
+`rps` scores day 2's strategy guide under the rock-paper-scissors variant
+described in FILE; see src/days/day02.rules.toml for the standard game, which
+is the default. --analyze instead scores every way of reading the second
+column as moves or as outcomes, best first, with how many letters each reads
+differently from FILE. It marks as likely intended the one that wins the most
+rounds without winning them all, then the one closest to FILE, then the lower
+scoring.
+`rucksacks` lists the lines of day 3's input that break the puzzle's rules:
+odd item counts, compartments that don't share exactly one item, and groups
+of K (default 3) without exactly one badge. It exits nonzero if there are any.
+`sections` prints, for each line of day 4's input, the sections between the
+lowest and highest that nobody has, the ones more than one elf has, and the
+most elves that all overlap.
+`overlaps` lists every assignment anywhere in day 4's input that shares a
+section with each <range>, given as 5-7, or as 5 for a single section.
+`crane` carries out day 5's moves with the given crane (default 9000, part 1's;
+9001 is part 2's) and draws the stacks at the end, or after the first K moves,
+followed by the crates on top. --trace also prints each move and the stacks
+after it.
+`markers` reads day 6's datastream a piece at a time and, for each marker size
+N (default 4 and 14), counts every place with N different characters in a row
+and splits the stream into messages: a marker, then everything up to the next
+marker that doesn't overlap it. Each message is printed as soon as it ends, as
+its size and the byte ranges of its marker and its body, counted from 0, end
+exclusive. The number of markers and messages of each size comes last.
+`du`, `find`, `largest` and `free` rebuild the filesystem from day 7's
+transcript. `du` prints each directory's total size, down to depth N (the
+root is 0). `find` prints the size and path of each file and directory whose
+name matches GLOB (with * and ?), whose size is between the limits, and which
+is of the given type. `largest` prints the N (default 10) biggest directories.
+`free` prints the directories to delete to have <bytes> free on a disk of
+N bytes (default 70000000): none inside another, deleting as little as
+possible between them. Part 2 is the smallest single such directory.
+`tree` prints the whole filesystem like `tree -s`, with each directory's size
+including everything in it. With --format json it prints the same as JSON, in
+the shape `tree -J` uses; with --format sh it prints a shell script that
+recreates it, with sparse files, in a new temporary directory.
+
+input options:
+  --input PATH      read the input from PATH, or stdin if PATH is - (single
+                    day only)
+  --input-dir DIR   read each day's input from DIR/dayNN.txt (default: inputs)
+
+With a single day and no --input, input is read from stdin if anything is
+piped in. Otherwise each day reads DIR/dayNN.txt, or dayNN.input from the
+current directory if that doesn't exist.";
+
+fn parse_days(arg: &str) -> Option<RangeInclusive<usize>> {
+    let range = if arg == "all" {
+        1..=25
+    }
+    else if let Some((start, end)) = arg.split_once("..") {
+        let end = end.strip_prefix('=').unwrap_or(end);
+        start.parse::<usize>().ok()? ..= end.parse::<usize>().ok()?
+    }
+    else {
+        let day = arg.parse::<usize>().ok()?;
+        day ..= day
+    };
+    if range.is_empty() || *range.start() < 1 || *range.end() > 25 {
+        return None;
+    }
+    Some(range)
+}
+
+/// A section range for `aoc overlaps`: `5-7`, or `5` for just one.
+fn parse_query(arg: &str) -> Option<RangeInclusive<u32>> {
+    let (start, end) = arg.split_once('-').unwrap_or((arg, arg));
+    let range = start.parse().ok()? ..= end.parse().ok()?;
+    if range.is_empty() {
+        return None;
+    }
+    Some(range)
+}
+
+#[derive(PartialEq, Eq)]
+enum Command {
+    Run,
+    Bench,
+    Verify,
+    Gen,
+    Check,
+    Elves,
+    Rps,
+    Rucksacks,
+    Sections,
+    Overlaps,
+    Crane,
+    Markers,
+    Du,
+    Find,
+    Largest,
+    Free,
+    Tree,
+}
+
+struct Options {
+    command: Command,
+    days: RangeInclusive<usize>,
+    source: input::Source,
+    time: bool,
+    iterations: usize,
+    answers: PathBuf,
+    json: bool,
+    size: Option<usize>,
+    seed: u64,
+    top: Option<usize>,
+    stats: bool,
+    stream: bool,
+    rules: Option<PathBuf>,
+    analyze: bool,
+    group: usize,
+    queries: Vec<RangeInclusive<u32>>,
+    model: u32,
+    trace: bool,
+    step: Option<usize>,
+    sizes: Vec<usize>,
+    depth: Option<usize>,
+    name: Option<String>,
+    min_size: Option<usize>,
+    max_size: Option<usize>,
+    is_dir: Option<bool>,
+    needed: Option<usize>,
+    disk: usize,
+    script: bool,
+}
+
+fn parse_args(args: &[String]) -> Option<Options> {
+    let (cmd, rest) = args.split_first()?;
+    let command = match cmd.as_str() {
+        "run" => Command::Run,
+        "bench" => Command::Bench,
+        "verify" => Command::Verify,
+        "gen" => Command::Gen,
+        "check" => Command::Check,
+        "elves" => Command::Elves,
+        "rps" => Command::Rps,
+        "rucksacks" => Command::Rucksacks,
+        "sections" => Command::Sections,
+        "overlaps" => Command::Overlaps,
+        "crane" => Command::Crane,
+        "markers" => Command::Markers,
+        "du" => Command::Du,
+        "find" => Command::Find,
+        "largest" => Command::Largest,
+        "free" => Command::Free,
+        "tree" => Command::Tree,
+        _ => return None,
+    };
+    let mut days = None;
+    let mut path = None;
+    let mut dir = None;
+    let mut time = false;
+    let mut iterations = None;
+    let mut answers = PathBuf::from(answers::DEFAULT_FILE);
+    let mut json = false;
+    let mut size = None;
+    let mut seed = 0;
+    let mut top = None;
+    let mut stats = false;
+    let mut stream = false;
+    let mut rules = None;
+    let mut analyze = false;
+    let mut group = 3;
+    let mut queries = Vec::new();
+    let mut model = 9000;
+    let mut trace = false;
+    let mut step = None;
+    let mut sizes = Vec::new();
+    let mut depth = None;
+    let mut name = None;
+    let mut min_size = None;
+    let mut max_size = None;
+    let mut is_dir = None;
+    let mut needed = None;
+    let mut disk = 70_000_000;
+    let mut script = false;
+    let mut iter = rest.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--input" => path = Some(PathBuf::from(iter.next()?)),
+            "--input-dir" => dir = Some(PathBuf::from(iter.next()?)),
+            "--time" if command == Command::Run => time = true,
+            "--format" if command == Command::Run => json = match iter.next()?.as_str() {
+                "text" => false,
+                "json" => true,
+                _ => return None,
+            },
+            "-n" if command == Command::Bench || command == Command::Check => iterations = Some(iter.next()?.parse().ok()?),
+            "--answers" if command == Command::Verify => answers = PathBuf::from(iter.next()?),
+            "--size" if command == Command::Gen => size = Some(iter.next()?.parse().ok()?),
+            "--seed" if command == Command::Gen || command == Command::Check => seed = iter.next()?.parse().ok()?,
+            "--top" if command == Command::Elves || command == Command::Largest => top = Some(iter.next()?.parse().ok()?),
+            "--stats" if command == Command::Elves => stats = true,
+            "--stream" if command == Command::Elves => stream = true,
+            "--rules" if command == Command::Rps => rules = Some(PathBuf::from(iter.next()?)),
+            "--analyze" if command == Command::Rps => analyze = true,
+            "--group" if command == Command::Rucksacks => group = iter.next()?.parse().ok()?,
+            "--model" if command == Command::Crane => model = match iter.next()?.as_str() {
+                "9000" => 9000,
+                "9001" => 9001,
+                _ => return None,
+            },
+            "--trace" if command == Command::Crane => trace = true,
+            "--step" if command == Command::Crane => step = Some(iter.next()?.parse().ok()?),
+            "--size" if command == Command::Markers => sizes.push(iter.next()?.parse().ok()?),
+            "--format" if command == Command::Tree => (json, script) = match iter.next()?.as_str() {
+                "text" => (false, false),
+                "json" => (true, false),
+                "sh" => (false, true),
+                _ => return None,
+            },
+            "--depth" if command == Command::Du => depth = Some(iter.next()?.parse().ok()?),
+            "--name" if command == Command::Find => name = Some(iter.next()?.clone()),
+            "--min" if command == Command::Find => min_size = Some(iter.next()?.parse().ok()?),
+            "--max" if command == Command::Find => max_size = Some(iter.next()?.parse().ok()?),
+            "--type" if command == Command::Find => is_dir = match iter.next()?.as_str() {
+                "f" => Some(false),
+                "d" => Some(true),
+                _ => return None,
+            },
+            "--disk" if command == Command::Free => disk = iter.next()?.parse().ok()?,
+            _ if command == Command::Free && needed.is_none() => needed = Some(arg.parse().ok()?),
+            _ if command == Command::Overlaps => queries.push(parse_query(arg)?),
+            _ if days.is_none() && ![Command::Elves, Command::Rps, Command::Rucksacks, Command::Sections, Command::Crane, Command::Markers,
+                Command::Du, Command::Find, Command::Largest, Command::Free, Command::Tree].contains(&command) => days = Some(parse_days(arg)?),
+            _ => return None,
+        }
+    }
+    let days = match days {
+        None if command == Command::Check => 1..=25,
+        None if command == Command::Elves => 1..=1,
+        None if command == Command::Rps => 2..=2,
+        None if command == Command::Rucksacks => 3..=3,
+        None if command == Command::Sections || command == Command::Overlaps => 4..=4,
+        None if command == Command::Crane => 5..=5,
+        None if command == Command::Markers => 6..=6,
+        None if [Command::Du, Command::Find, Command::Largest, Command::Free, Command::Tree].contains(&command) => 7..=7,
+        days => days?,
+    };
+    let single = days.start() == days.end();
+    if command == Command::Gen && !single {
+        return None;
+    }
+    let source = match (path, dir) {
+        (Some(_), Some(_)) => return None,
+        (Some(_), None) if !single => return None,
+        (Some(path), None) if path.as_os_str() == "-" => input::Source::Stdin,
+        (Some(path), None) => input::Source::Path(path),
+        (None, Some(dir)) => input::Source::Dir(dir),
+        (None, None) if single && !io::stdin().is_terminal() => input::Source::StdinOr(input::DEFAULT_DIR.into()),
+        (None, None) => input::Source::default(),
+    };
+    let iterations = iterations.unwrap_or(if command == Command::Check { 100 } else { 10 });
+    if iterations == 0 || top == Some(0) || (stats && stream) || group == 0
+        || (command == Command::Overlaps && queries.is_empty()) || sizes.contains(&0) || !sizes.iter().all_unique()
+        || (command == Command::Free && needed.is_none()) {
+        return None;
+    }
+    if sizes.is_empty() {
+        sizes = vec![4, 14];
+    }
+    let top = if stats { top } else { top.or(Some(if command == Command::Largest { 10 } else { 3 })) };
+    Some(Options { command, days, source, time, iterations, answers, json, size, seed, top, stats, stream, rules, analyze, group, queries, model, trace, step, sizes,
+        depth, name, min_size, max_size, is_dir, needed, disk, script })
+}
+
+/// Calls `f` for each day, all at once on a thread pool when built with the
+/// `parallel` feature. The results stay in day order either way.
+fn map_days<T, F>(days: RangeInclusive<usize>, f: F) -> Vec<T>
+where T: Send, F: Fn(usize) -> T + Sync + Send {
+    #[cfg(feature = "parallel")]
+    let results = days.into_par_iter().map(f).collect();
+    #[cfg(not(feature = "parallel"))]
+    let results = days.map(f).collect();
+    results
+}
+
+/// Reads a day's input and hands it to `f` along with the day's solver.
+fn read_and_run<T, F>(day: usize, source: &input::Source, f: F) -> Result<T, String>
+where F: FnOnce(&dyn Runner, &str) -> Result<T, ParseError> {
+    let solver = days::get(day).unwrap();
+    input::read(day, source)
+        .map_err(|e| format!("no input: {e}"))
+        .and_then(|input| f(solver, &input).map_err(|e| format!("parse error: {e}")))
+}
+
+struct Row {
+    day: usize,
+    answers: Result<(Answers, Timings), String>,
+}
+
+fn print_table(rows: &[Row], time: bool) {
+    let cell = |s: &str| if s.contains('\n') { "(see below)".to_string() } else { s.to_string() };
+    let width = |header: &str, part: fn(&Answers) -> Option<&String>| rows.iter()
+        .filter_map(|row| row.answers.as_ref().ok())
+        .filter_map(|(a, _)| part(a))
+        .map(|s| cell(s).len())
+        .chain([header.len()])
+        .max()
+        .unwrap();
+    let w1 = width("Part 1", |a| Some(&a.part1));
+    let w2 = width("Part 2", |a| a.part2.as_ref());
+    let ms = |d: Option<Duration>| d.map_or("-".into(), |d| format!("{:.3}", d.as_secs_f64() * 1000.0));
+
+    if time {
+        println!("Day  {:<w1$}  {:<w2$}  {:>10}  {:>10}  {:>10}", "Part 1", "Part 2", "Parse ms", "Part 1 ms", "Part 2 ms");
+    }
+    else {
+        println!("Day  {:<w1$}  Part 2", "Part 1");
+    }
+    for row in rows {
+        let (a, t) = match &row.answers {
+            Ok(r) => r,
+            Err(e) => {
+                println!("{:>3}  {e}", row.day);
+                continue;
+            },
+        };
+        let p2 = a.part2.as_deref().map(cell).unwrap_or_else(|| "-".into());
+        if time {
+            println!("{:>3}  {:<w1$}  {p2:<w2$}  {:>10}  {:>10}  {:>10}", row.day, cell(&a.part1),
+                ms(Some(t.parse)), ms(Some(t.part1)), ms(t.part2));
+        }
+        else {
+            println!("{:>3}  {:<w1$}  {p2}", row.day, cell(&a.part1));
+        }
+        for art in [Some(&a.part1), a.part2.as_ref()].into_iter().flatten() {
+            if art.contains('\n') {
+                for line in art.lines() {
+                    println!("     {line}");
+                }
+            }
+        }
+    }
+}
+
+fn print_json(rows: &[Row]) {
+    for row in rows {
+        let obj = match &row.answers {
+            Ok((a, t)) => {
+                let kinds = days::get(row.day).unwrap().answer_kinds();
+                let parts = [(1, Some(&a.part1), Some(t.part1)), (2, a.part2.as_ref(), t.part2)]
+                    .into_iter()
+                    .filter_map(|(part, answer, elapsed)| Some(json::object! {
+                        part: part,
+                        answer: answer?.as_str(),
+                        type: kinds[part - 1].name(),
+                        elapsed_ns: elapsed?.as_nanos() as u64,
+                    }))
+                    .collect::<Vec<_>>();
+                json::object! {
+                    day: row.day,
+                    parse_ns: t.parse.as_nanos() as u64,
+                    parts: parts,
+                }
+            },
+            Err(e) => json::object! { day: row.day, error: e.as_str() },
+        };
+        println!("{}", obj.dump());
+    }
+}
+
+/// Min, median and max, in nanoseconds.
+fn stats(mut samples: Vec<Duration>) -> (u128, u128, u128) {
+    samples.sort();
+    let ns = |d: &Duration| d.as_nanos();
+    (ns(&samples[0]), ns(&samples[samples.len() / 2]), ns(&samples[samples.len() - 1]))
+}
+
+fn bench(opts: &Options) -> bool {
+    let mut ok = true;
+    println!("day\tstage\truns\tmin_ns\tmedian_ns\tmax_ns");
+    for day in opts.days.clone() {
+        let runs = match read_and_run(day, &opts.source, |s, input| s.bench(input, opts.iterations)) {
+            Ok(runs) => runs,
+            Err(e) => {
+                eprintln!("day {day}: {e}");
+                ok = false;
+                continue;
+            },
+        };
+        let stage_times = |t: &Timings| [Some(t.parse), Some(t.part1), t.part2];
+        for (idx, stage) in ["parse", "part1", "part2"].into_iter().enumerate() {
+            let samples: Vec<Duration> = runs.iter().filter_map(|t| stage_times(t)[idx]).collect();
+            if samples.is_empty() {
+                continue;
+            }
+            let (min, median, max) = stats(samples);
+            println!("{day}\t{stage}\t{}\t{min}\t{median}\t{max}", runs.len());
+        }
+    }
+    ok
+}
+
+fn verify(opts: &Options) -> bool {
+    let key = match AnswerKey::load(&opts.answers) {
+        Ok(key) => key,
+        Err(e) => {
+            eprintln!("{e}");
+            return false;
+        },
+    };
+    let mut ok = true;
+    println!("Day  Part  Result");
+    let results = map_days(opts.days.clone(), |day| read_and_run(day, &opts.source, |s, input| s.run(input)));
+    for (day, answers) in opts.days.clone().zip(results) {
+        let answers = match answers {
+            Ok(a) => a,
+            Err(e) => {
+                println!("{day:>3}        {e}");
+                ok = false;
+                continue;
+            },
+        };
+        for (part, answer) in [(1, Some(&answers.part1)), (2, answers.part2.as_ref())] {
+            let Some(answer) = answer else { continue };
+            match key.check(day as u32, part, answer) {
+                Verdict::Pass => println!("{day:>3}  {part:>4}  PASS"),
+                Verdict::Missing => println!("{day:>3}  {part:>4}  MISSING  got {answer}"),
+                Verdict::Fail { expected } if !expected.contains('\n') && !answer.contains('\n') => {
+                    println!("{day:>3}  {part:>4}  FAIL     expected {expected}, got {answer}");
+                    ok = false;
+                },
+                Verdict::Fail { expected } => {
+                    println!("{day:>3}  {part:>4}  FAIL");
+                    for (label, text) in [("expected", &expected), ("got", answer)] {
+                        println!("     {label}:");
+                        for line in text.lines() {
+                            println!("     {line}");
+                        }
+                    }
+                    ok = false;
+                },
+            }
+        }
+    }
+    ok
+}
+
+fn check(opts: &Options) -> bool {
+    let checkers: Vec<_> = days::CHECKERS.iter().filter(|(day, _)| opts.days.contains(day)).collect();
+    if checkers.is_empty() {
+        eprintln!("no brute-force reference for those days");
+        return false;
+    }
+    let mut ok = true;
+    for (day, check) in checkers {
+        let failed = (0..opts.iterations as u64)
+            .map(|run| opts.seed + run)
+            .find_map(|seed| check(&mut Rng::new(seed)).err().map(|m| (seed, m)));
+        match failed {
+            None => println!("day {day}: {} runs agree", opts.iterations),
+            Some((seed, mismatch)) => {
+                println!("day {day}: seed {seed}: {} is {} but the reference says {}",
+                    mismatch.what, mismatch.solver, mismatch.reference);
+                for line in mismatch.input.lines() {
+                    println!("    {line}");
+                }
+                ok = false;
+            },
+        }
+    }
+    ok
+}
+
+fn elves(opts: &Options) -> bool {
+    if opts.stream {
+        return stream_elves(opts);
+    }
+    let groups = match input::read(1, &opts.source) {
+        Ok(text) => match Day01.parse(&text) {
+            Ok(groups) => groups,
+            Err(e) => {
+                eprintln!("parse error: {}", e.for_day(1));
+                return false;
+            },
+        },
+        Err(e) => {
+            eprintln!("no input: {e}");
+            return false;
+        },
+    };
+    if let Some(n) = opts.top {
+        // Parsing has checked every elf's total, so this can't fail.
+        let top = day01::top_elves(&groups, n).unwrap();
+        println!("Rank    Elf    Total  Items");
+        for (rank, elf) in top.iter().enumerate() {
+            println!("{:>4}  {:>5}  {:>7}  {}", rank + 1, elf.index + 1, elf.total, elf.items.iter().join(" "));
+        }
+    }
+    if opts.stats {
+        let Some(stats) = day01::stats(&groups, 10).unwrap() else {
+            eprintln!("no elves");
+            return false;
+        };
+        if opts.top.is_some() {
+            println!();
+        }
+        println!("elves   {}", stats.elves);
+        println!("mean    {:.1}", stats.mean);
+        println!("median  {:.1}", stats.median);
+        let most = stats.histogram.iter().map(|(_, count)| *count).max().unwrap();
+        for (range, count) in &stats.histogram {
+            let bar = "#".repeat((count * 40).div_ceil(most));
+            let line = format!("{:>7}..{:<7} {count:>5} {bar}", range.start, range.end);
+            println!("{}", line.trim_end());
+        }
+    }
+    true
+}
+
+fn stream_elves(opts: &Options) -> bool {
+    let leaders = input::open(1, &opts.source)
+        .map_err(|e| format!("no input: {e}"))
+        .and_then(|reader| day01::stream_top_elves(reader, opts.top.unwrap()).map_err(|e| {
+            match e.get_ref().and_then(|e| e.downcast_ref::<ParseError>()) {
+                Some(e) => format!("parse error: {}", e.clone().for_day(1)),
+                None => format!("read error: {e}"),
+            }
+        }));
+    match leaders {
+        Ok(leaders) => {
+            println!("Rank    Elf    Total");
+            for (rank, elf) in leaders.iter().enumerate() {
+                println!("{:>4}  {:>5}  {:>7}", rank + 1, elf.index + 1, elf.total);
+            }
+            true
+        },
+        Err(e) => {
+            eprintln!("{e}");
+            false
+        },
+    }
+}
+
+fn rps(opts: &Options) -> bool {
+    let rules = match &opts.rules {
+        Some(path) => day02::Rules::load(path),
+        None => Ok(day02::Rules::standard()),
+    };
+    let done = rules.and_then(|rules| {
+        let text = input::read(2, &opts.source).map_err(|e| format!("no input: {e}"))?;
+        let parse_error = |e: ParseError| format!("parse error: {}", e.for_day(2));
+        if opts.analyze {
+            print_analysis(&day02::analyze(&rules, &text).map_err(parse_error)?);
+        }
+        else {
+            let (part1, part2) = day02::play(&rules, &text).map_err(parse_error)?;
+            println!("Part 1  {part1}");
+            println!("Part 2  {part2}");
+        }
+        Ok(())
+    });
+    if let Err(e) = &done {
+        eprintln!("{e}");
+    }
+    done.is_ok()
+}
+
+fn print_analysis(analysis: &day02::Analysis) {
+    let mapping = |m: &day02::Mapping| m.letters.iter().map(|(letter, meaning)| format!("{letter}={meaning}")).join(" ");
+    let width = analysis.mappings.iter().map(|m| mapping(m).len()).chain(["Mapping".len()]).max().unwrap();
+    println!("Reading   {:<width$}  Changed  Score    Wins", "Mapping");
+    for (idx, m) in analysis.mappings.iter().enumerate() {
+        let reading = match m.reading {
+            day02::Reading::Moves => "moves",
+            day02::Reading::Outcomes => "outcomes",
+        };
+        let wins = format!("{}/{}", m.wins, m.rounds);
+        let line = format!("{reading:<8}  {:<width$}  {:>7}  {:>5}  {wins:>6}  {}",
+            mapping(m), m.distance, m.score, if analysis.likely == Some(idx) { "<- likely" } else { "" });
+        println!("{}", line.trim_end());
+    }
+}
+
+fn rucksacks(opts: &Options) -> bool {
+    let report = input::read(3, &opts.source)
+        .map_err(|e| format!("no input: {e}"))
+        .and_then(|text| Day03.parse(&text).map_err(|e| format!("parse error: {}", e.for_day(3))))
+        .map(|input| day03::diagnose(&input, opts.group));
+    match report {
+        Ok(report) if report.is_empty() => {
+            println!("no problems");
+            true
+        },
+        Ok(report) => {
+            for diagnostic in report {
+                println!("{diagnostic}");
+            }
+            false
+        },
+        Err(e) => {
+            eprintln!("{e}");
+            false
+        },
+    }
+}
+
+fn sections(opts: &Options) -> bool {
+    let groups = input::read(4, &opts.source)
+        .map_err(|e| format!("no input: {e}"))
+        .and_then(|text| Day04.parse(&text).map_err(|e| format!("parse error: {}", e.for_day(4))));
+    let groups = match groups {
+        Ok(groups) => groups,
+        Err(e) => {
+            eprintln!("{e}");
+            return false;
+        },
+    };
+    let rows: Vec<[String; 4]> = groups.iter().enumerate()
+        .map(|(idx, group)| {
+            let clique = match group.largest_clique() {
+                Some(c) => format!("{} at {} (elves {})", c.elves.len(), c.section, c.elves.iter().map(|e| e + 1).join(",")),
+                None => "-".into(),
+            };
+            [(idx + 1).to_string(), group.uncovered().to_string(), group.shared().to_string(), clique]
+        })
+        .collect();
+    let headers = ["Line", "Uncovered", "Shared", "Largest overlap"];
+    let widths: Vec<usize> = (0..4)
+        .map(|col| rows.iter().map(|r| r[col].len()).chain([headers[col].len()]).max().unwrap())
+        .collect();
+    println!("{:>w0$}  {:<w1$}  {:<w2$}  {}", headers[0], headers[1], headers[2], headers[3],
+        w0 = widths[0], w1 = widths[1], w2 = widths[2]);
+    for row in &rows {
+        println!("{:>w0$}  {:<w1$}  {:<w2$}  {}", row[0], row[1], row[2], row[3],
+            w0 = widths[0], w1 = widths[1], w2 = widths[2]);
+    }
+    true
+}
+
+fn overlaps(opts: &Options) -> bool {
+    let groups = input::read(4, &opts.source)
+        .map_err(|e| format!("no input: {e}"))
+        .and_then(|text| Day04.parse(&text).map_err(|e| format!("parse error: {}", e.for_day(4))));
+    let index = match groups {
+        Ok(groups) => SectionIndex::new(&groups),
+        Err(e) => {
+            eprintln!("{e}");
+            return false;
+        },
+    };
+    for query in &opts.queries {
+        let mut found = index.overlapping(query.clone());
+        found.sort_by_key(|a| (a.line, a.elf));
+        println!("{}-{}: {} assignments", query.start(), query.end(), found.len());
+        for a in found {
+            println!("  line {}, elf {}: {}-{}", a.line, a.elf + 1, a.range.start(), a.range.end());
+        }
+    }
+    true
+}
+
+fn crane(opts: &Options) -> bool {
+    let input = input::read(5, &opts.source)
+        .map_err(|e| format!("no input: {e}"))
+        .and_then(|text| Day05.parse(&text).map_err(|e| format!("parse error: {}", e.for_day(5))));
+    let (stacks, moves) = match input {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("{e}");
+            return false;
+        },
+    };
+    let steps = opts.step.unwrap_or(moves.len());
+    if steps > moves.len() {
+        eprintln!("there are only {} moves", moves.len());
+        return false;
+    }
+    let crane: &dyn Crane = if opts.model == 9000 { &CrateMover9000 } else { &CrateMover9001 };
+    let mut yard = CrateYard::new(&stacks);
+    for mv in &moves[..steps] {
+        match yard.apply(crane, mv) {
+            Ok(step) if opts.trace => println!("{step}\n{}", yard.render()),
+            Ok(_) => {},
+            Err(e) => {
+                eprintln!("{e}");
+                return false;
+            },
+        }
+    }
+    print!("{}", yard.render());
+    println!("{}", yard.tops());
+    true
+}
+
+fn markers(opts: &Options) -> bool {
+    // Markers, then messages, for each size.
+    let mut counts = vec![(0, 0); opts.sizes.len()];
+    let scanned = input::open(6, &opts.source)
+        .map_err(|e| format!("no input: {e}"))
+        .and_then(|reader| day06::scan_markers(reader, &opts.sizes, |found| match found {
+            day06::Found::Marker { size, .. } => counts[opts.sizes.iter().position(|s| *s == size).unwrap()].0 += 1,
+            day06::Found::Message { size, message } => {
+                counts[opts.sizes.iter().position(|s| *s == size).unwrap()].1 += 1;
+                println!("{size}  {}..{}  {}..{}", message.marker.start, message.marker.end, message.body.start, message.body.end);
+            },
+        }).map_err(|e| format!("read error: {e}")));
+    if let Err(e) = scanned {
+        eprintln!("{e}");
+        return false;
+    }
+    for (size, (markers, messages)) in opts.sizes.iter().zip(counts) {
+        println!("size {size}: {markers} markers, {messages} messages");
+    }
+    true
+}
+
+fn read_tree(opts: &Options) -> Option<DirTree> {
+    let tree = input::read(7, &opts.source)
+        .map_err(|e| format!("no input: {e}"))
+        .and_then(|text| Day07.parse(&text).map_err(|e| format!("parse error: {}", e.for_day(7))));
+    tree.map_err(|e| eprintln!("{e}")).ok()
+}
+
+fn du(opts: &Options) -> bool {
+    let Some(tree) = read_tree(opts) else { return false };
+    for (dir, _) in tree.du(opts.depth) {
+        println!("{}\t{}", tree.size(dir), tree.path(dir));
+    }
+    true
+}
+
+fn find(opts: &Options) -> bool {
+    let Some(tree) = read_tree(opts) else { return false };
+    let found = tree.find(|e| {
+        let name = e.path.rsplit('/').next().unwrap();
+        opts.name.as_ref().is_none_or(|glob| day07::glob_match(glob, name))
+            && opts.min_size.is_none_or(|min| e.size >= min)
+            && opts.max_size.is_none_or(|max| e.size <= max)
+            && opts.is_dir.is_none_or(|is_dir| e.is_dir == is_dir)
+    });
+    for entry in found {
+        println!("{}\t{}", entry.size, entry.path);
+    }
+    true
+}
+
+fn largest(opts: &Options) -> bool {
+    let Some(tree) = read_tree(opts) else { return false };
+    for dir in tree.largest(opts.top.unwrap()) {
+        println!("{}\t{}", tree.size(dir), tree.path(dir));
+    }
+    true
+}
+
+fn free(opts: &Options) -> bool {
+    let Some(tree) = read_tree(opts) else { return false };
+    let needed = opts.needed.unwrap();
+    match tree.to_free(opts.disk, needed) {
+        Some(dirs) if dirs.is_empty() => println!("already {} bytes free", opts.disk - tree.size(DirTree::ROOT)),
+        Some(dirs) => {
+            for dir in &dirs {
+                println!("{}\t{}", tree.size(*dir), tree.path(*dir));
+            }
+            println!("{} bytes freed", dirs.iter().map(|d| tree.size(*d)).sum::<usize>());
+        },
+        None if tree.size(DirTree::ROOT) > opts.disk => {
+            eprintln!("{} bytes in use, more than the disk's {}", tree.size(DirTree::ROOT), opts.disk);
+            return false;
+        },
+        None => {
+            eprintln!("a disk of {} bytes can't have {needed} free", opts.disk);
+            return false;
+        },
+    }
+    true
+}
+
+fn tree(opts: &Options) -> bool {
+    let Some(tree) = read_tree(opts) else { return false };
+    if opts.json {
+        println!("{}", tree.to_json().dump());
+    }
+    else if opts.script {
+        print!("{}", tree.to_script());
+    }
+    else {
+        println!("{tree}");
+    }
+    true
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let Some(opts) = parse_args(&args) else {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    };
+
+    let ok = match opts.command {
+        Command::Run => {
+            let rows: Vec<Row> = map_days(opts.days.clone(), |day| {
+                Row { day, answers: read_and_run(day, &opts.source, |s, input| s.run_timed(input)) }
+            });
+            if opts.json {
+                print_json(&rows);
+            }
+            else {
+                print_table(&rows, opts.time);
+            }
+            rows.iter().all(|r| r.answers.is_ok())
+        },
+        Command::Bench => bench(&opts),
+        Command::Verify => verify(&opts),
+        Command::Gen => {
+            let generator = days::generator(*opts.days.start()).unwrap();
+            let size = opts.size.unwrap_or(generator.default_size);
+            print!("{}", (generator.generate)(&mut Rng::new(opts.seed), size));
+            true
+        },
+        Command::Check => check(&opts),
+        Command::Elves => elves(&opts),
+        Command::Rps => rps(&opts),
+        Command::Rucksacks => rucksacks(&opts),
+        Command::Sections => sections(&opts),
+        Command::Overlaps => overlaps(&opts),
+        Command::Crane => crane(&opts),
+        Command::Markers => markers(&opts),
+        Command::Du => du(&opts),
+        Command::Find => find(&opts),
+        Command::Largest => largest(&opts),
+        Command::Free => free(&opts),
+        Command::Tree => tree(&opts),
+    };
+    if ok {
+        ExitCode::SUCCESS
+    }
+    else {
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,25 @@
+use std::fmt::Display;
+
+/// A disagreement between a day's solver and its brute-force reference.
+#[derive(Debug)]
+pub struct Mismatch {
+    pub what: String,
+    pub solver: String,
+    pub reference: String,
+    pub input: String,
+}
+
+/// Compares the solver's answer with the reference's, keeping the input so
+/// that a disagreement can be reproduced.
+pub fn compare<T>(input: &str, what: &str, solver: T, reference: T) -> Result<(), Mismatch>
+where T: PartialEq + Display {
+    if solver == reference {
+        return Ok(());
+    }
+    Err(Mismatch {
+        what: what.into(),
+        solver: solver.to_string(),
+        reference: reference.to_string(),
+        input: input.into(),
+    })
+}
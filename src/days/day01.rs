@@ -0,0 +1,251 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::io::{self, BufRead};
+use std::ops::Range;
+use std::vec::Vec;
+use itertools::Itertools;
+use crate::{ParseError, Solution};
+use crate::parse::{groups_with, parse_at};
+use crate::rng::Rng;
+
+/// One elf's food: where its group is in the input (from 0), the calories
+/// it adds up to, and the items themselves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Elf<'a> {
+    pub index: usize,
+    pub total: i64,
+    pub items: &'a [i64],
+}
+
+/// An elf found by `stream_top_elves`, which doesn't keep the items.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ElfTotal {
+    pub index: usize,
+    pub total: i64,
+}
+
+/// The `n` biggest totals offered so far; ties go to the elf that came first.
+/// A min-heap of at most `n` elves, so nothing is sorted but the winners.
+struct Leaders {
+    n: usize,
+    heap: BinaryHeap<Reverse<(i64, Reverse<usize>)>>,
+}
+
+impl Leaders {
+    fn new(n: usize) -> Self {
+        Leaders { n, heap: BinaryHeap::with_capacity(n + 1) }
+    }
+
+    fn offer(&mut self, index: usize, total: i64) {
+        self.heap.push(Reverse((total, Reverse(index))));
+        if self.heap.len() > self.n {
+            self.heap.pop();
+        }
+    }
+
+    /// Most first.
+    fn into_vec(self) -> Vec<ElfTotal> {
+        self.heap.into_sorted_vec().into_iter()
+            .map(|Reverse((total, Reverse(index)))| ElfTotal { index, total })
+            .collect()
+    }
+}
+
+/// The `n` elves carrying the most calories, most first; ties go to the elf
+/// that comes first. Fails like `totals` does.
+pub fn top_elves(groups: &[Vec<i64>], n: usize) -> Result<Vec<Elf<'_>>, ParseError> {
+    let mut leaders = Leaders::new(n);
+    for (index, total) in totals(groups)?.into_iter().enumerate() {
+        leaders.offer(index, total);
+    }
+    Ok(leaders.into_vec().into_iter()
+        .map(|ElfTotal { index, total }| Elf { index, total, items: &groups[index] })
+        .collect())
+}
+
+/// Each elf's total, or an error at the line, counted the way the input
+/// numbers it, of the item that takes it out of i64.
+fn totals(groups: &[Vec<i64>]) -> Result<Vec<i64>, ParseError> {
+    let mut line = 0;
+    let mut totals = Vec::with_capacity(groups.len());
+    for group in groups {
+        let mut total: i64 = 0;
+        for item in group {
+            line += 1;
+            total = total.checked_add(*item).ok_or_else(|| overflow(line, *item))?;
+        }
+        line += 1;
+        totals.push(total);
+    }
+    Ok(totals)
+}
+
+fn overflow(line: usize, item: i64) -> ParseError {
+    ParseError::new(1, item.to_string(), "a calorie count that keeps the elf's total within i64").at_line(line)
+}
+
+/// Like `top_elves`, but reads the inventory a line at a time and keeps only
+/// the leaders, for inputs too big to load. Parse errors, including an elf
+/// whose total overflows, come back as `InvalidData` wrapping a `ParseError`.
+pub fn stream_top_elves<R: BufRead>(reader: R, n: usize) -> io::Result<Vec<ElfTotal>> {
+    let invalid = |e: ParseError| io::Error::new(io::ErrorKind::InvalidData, e);
+    let mut leaders = Leaders::new(n);
+    let mut index = 0;
+    let mut total: Option<i64> = None;
+    // Blank lines only end a group once something follows them, so that
+    // trailing ones are ignored the way `groups_with` ignores them.
+    let mut blanks = 0;
+    for (idx, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line = line.trim_end_matches('\r');
+        if line.is_empty() {
+            blanks += 1;
+            continue;
+        }
+        for _ in 0..blanks {
+            leaders.offer(index, total.take().unwrap_or(0));
+            index += 1;
+        }
+        blanks = 0;
+        let item: i64 = parse_at(line, 1, "a calorie count").map_err(|e| invalid(e.at_line(idx + 1)))?;
+        total = Some(total.unwrap_or(0).checked_add(item).ok_or_else(|| invalid(overflow(idx + 1, item)))?);
+    }
+    if let Some(total) = total {
+        leaders.offer(index, total);
+    }
+    Ok(leaders.into_vec())
+}
+
+/// How the per-elf totals are spread out.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub elves: usize,
+    pub mean: f64,
+    pub median: f64,
+    /// Equal-width buckets from the smallest total to the largest, with the
+    /// number of elves in each.
+    pub histogram: Vec<(Range<i64>, usize)>,
+}
+
+/// Totals up every elf and summarizes them with `buckets` histogram buckets.
+/// Returns `None` if there are no elves, and fails like `totals` does. The
+/// sums in between are done in i128, so any i64 totals fit; the last
+/// bucket's end stops at `i64::MAX`.
+pub fn stats(groups: &[Vec<i64>], buckets: usize) -> Result<Option<Stats>, ParseError> {
+    let totals: Vec<i128> = totals(groups)?.into_iter().map(i128::from).sorted().collect();
+    let (Some(&min), Some(&max)) = (totals.first(), totals.last()) else { return Ok(None) };
+    let elves = totals.len();
+    let mean = totals.iter().sum::<i128>() as f64 / elves as f64;
+    let median = (totals[(elves - 1) / 2] + totals[elves / 2]) as f64 / 2.0;
+    let width = (max - min) / buckets.max(1) as i128 + 1;
+    let bound = |n: i128| i64::try_from(n).unwrap_or(i64::MAX);
+    let mut histogram: Vec<(Range<i64>, usize)> = (0..buckets.max(1) as i128)
+        .map(|b| (bound(min + b * width) .. bound(min + (b + 1) * width), 0))
+        .take_while(|(range, _)| i128::from(range.start) <= max)
+        .collect();
+    for total in &totals {
+        histogram[((total - min) / width) as usize].1 += 1;
+    }
+    Ok(Some(Stats { elves, mean, median, histogram }))
+}
+
+fn part1(input: &[Vec<i64>]) -> i64 {
+    input.iter().map(|row| row.iter().sum::<i64>()).max().unwrap()
+}
+
+fn part2(input: &[Vec<i64>]) -> i64 {
+    // Parsing made sure both the totals and this sum fit.
+    top_elves(input, 3).unwrap().iter().map(|elf| elf.total).sum()
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<Vec<i64>>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    const DAY: u32 = 1;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        let groups = groups_with(input, |l| parse_at(l, 1, "a calorie count"))?;
+        if groups.is_empty() {
+            return Err(ParseError::new(1, "", "a calorie count").at_line(1));
+        }
+        // Part 2 adds up the top three, so that has to fit as well.
+        let mut sum: i64 = 0;
+        for elf in top_elves(&groups, 3)? {
+            sum = sum.checked_add(elf.total).ok_or_else(|| {
+                let line = groups[..elf.index].iter().map(|g| g.len() + 1).sum::<usize>() + 1;
+                ParseError::new(1, elf.items[0].to_string(), "calorie counts that keep the top three elves' sum within i64")
+                    .at_line(line)
+            })?;
+        }
+        Ok(groups)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+}
+
+/// Random input with `size` elves.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            let items = rng.range(1..=15);
+            (0..items).map(|_| format!("{}\n", rng.range(1000..=60000))).collect::<String>()
+        })
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_advent_lib::read::grouped_test_input;
+
+    #[test]
+    fn day01_test() {
+        let input: Vec<Vec<i64>> = grouped_test_input(include_str!("day01.testinput"));
+        assert_eq!(part1(&input), 24000);
+        assert_eq!(part2(&input), 45000);
+
+        let top = top_elves(&input, 2).unwrap();
+        assert_eq!(top, vec![
+            Elf { index: 3, total: 24000, items: &[7000, 8000, 9000] },
+            Elf { index: 2, total: 11000, items: &[5000, 6000] },
+        ]);
+
+        let stats = stats(&input, 4).unwrap().unwrap();
+        assert_eq!((stats.elves, stats.mean, stats.median), (5, 11000.0, 10000.0));
+        let streamed = stream_top_elves(include_str!("day01.testinput").as_bytes(), 2).unwrap();
+        assert_eq!(streamed, vec![ElfTotal { index: 3, total: 24000 }, ElfTotal { index: 2, total: 11000 }]);
+
+        let big = format!("1\n\n{}\n1\n", i64::MAX);
+        assert_eq!(Day01.parse(&big).unwrap_err().line, 4);
+        assert_eq!(Day01.parse("\n").unwrap_err().expected, "a calorie count");
+        let err = stream_top_elves(big.as_bytes(), 2).unwrap_err();
+        assert_eq!(err.into_inner().unwrap().downcast::<ParseError>().unwrap().line, 4);
+
+        assert_eq!(stats.histogram, vec![(4000..9001, 2), (9001..14002, 2), (14002..19003, 0), (19003..24004, 1)]);
+
+        // Each elf fits, but not the top three together.
+        let half = i64::MAX / 2;
+        let tall = format!("{half}\n\n1\n{half}\n\n{half}\n");
+        let err = Day01.parse(&tall).unwrap_err();
+        assert_eq!((err.line, err.text), (6, half.to_string()));
+        let groups = vec![vec![half, 1], vec![half], vec![i64::MIN], vec![1, i64::MAX]];
+        assert_eq!(top_elves(&groups, 2).unwrap_err().line, 9);
+        assert_eq!(super::stats(&groups, 2).unwrap_err().line, 9);
+        let groups = &groups[..3];
+        assert_eq!(top_elves(groups, 2).unwrap().iter().map(|e| e.index).collect::<Vec<usize>>(), vec![0, 1]);
+        assert_eq!(super::stats(groups, 2).unwrap().unwrap().median, half as f64);
+        let spread = super::stats(&[vec![i64::MIN], vec![i64::MAX]], 2).unwrap().unwrap();
+        assert_eq!((spread.mean, spread.median), (-0.5, -0.5));
+        assert_eq!(spread.histogram, vec![(i64::MIN..0, 1), (0..i64::MAX, 1)]);
+    }
+}
@@ -0,0 +1,466 @@
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::vec::Vec;
+use itertools::Itertools;
+use lazy_static::lazy_static;
+use toml::{Table, Value};
+use crate::{ParseError, Solution};
+use crate::parse::{lines, lines_with};
+use crate::rng::Rng;
+
+/// A move, by its position in `Rules::moves`.
+type Move = usize;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+enum Outcome {
+    Win,
+    Lose,
+    Draw,
+}
+
+impl Outcome {
+    const ALL: [Outcome; 3] = [Outcome::Lose, Outcome::Draw, Outcome::Win];
+
+    fn name(self) -> &'static str {
+        match self {
+            Outcome::Win => "win",
+            Outcome::Lose => "lose",
+            Outcome::Draw => "draw",
+        }
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "win" => Ok(Outcome::Win),
+            "lose" => Ok(Outcome::Lose),
+            "draw" => Ok(Outcome::Draw),
+            _ => Err(format!("unknown outcome `{s}`; expected win, lose or draw")),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct RawTurn {
+    opponent: String,
+    me: String,
+}
+
+/// "A, B or C"
+fn letter_list(map: &HashMap<String, impl Sized>) -> String {
+    let mut letters: Vec<&str> = map.keys().map(|k| k.as_str()).collect();
+    letters.sort_unstable();
+    match letters.split_last() {
+        Some((last, [])) => last.to_string(),
+        Some((last, rest)) => format!("{} or {last}", rest.join(", ")),
+        None => String::new(),
+    }
+}
+
+impl RawTurn {
+    fn parse_with(rules: &Rules, s: &str) -> Result<Self, ParseError> {
+        let turn = RawTurn::parse_opponent(rules, s)?;
+        if !rules.my_map.contains_key(&turn.me) {
+            return Err(ParseError::new(turn.opponent.len() + 2, turn.me, letter_list(&rules.my_map)));
+        }
+        Ok(turn)
+    }
+
+    /// Like `parse_with`, but takes any letter in the second column.
+    fn parse_opponent(rules: &Rules, s: &str) -> Result<Self, ParseError> {
+        if let Some((opponent, me)) = s.split_once(' ') {
+            if !rules.opponent_map.contains_key(opponent) {
+                return Err(ParseError::new(1, opponent, letter_list(&rules.opponent_map)));
+            }
+            if me.is_empty() || me.contains(' ') {
+                return Err(ParseError::new(opponent.len() + 2, me, "a single letter"));
+            }
+            Ok(
+                RawTurn {
+                    opponent: opponent.into(),
+                    me: me.into(),
+                }
+            )
+        }
+        else {
+            Err(ParseError::new(1, s, "two letters separated by a space"))
+        }
+    }
+}
+
+impl FromStr for RawTurn {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        RawTurn::parse_with(&RULES, s)
+    }
+}
+
+/// A rock-paper-scissors variant, loaded from a TOML file like
+/// `day02.rules.toml`. Validation makes sure every letter means something and
+/// that every outcome can be reached against every move, so scoring can't fail.
+#[derive(Clone)]
+pub struct Rules {
+    moves: Vec<String>,
+    /// `beats[a][b]` if move `a` beats move `b`.
+    beats: Vec<Vec<bool>>,
+    move_scoring: Vec<i32>,
+    outcome_scoring: HashMap<Outcome, i32>,
+    opponent_map: HashMap<String, Move>,
+    my_map: HashMap<String, Move>,
+    outcome_map: HashMap<String, Outcome>,
+}
+
+lazy_static! {
+    static ref RULES: Rules = include_str!("day02.rules.toml").parse().unwrap();
+}
+
+fn section<'a>(table: &'a Table, name: &str) -> Result<&'a Table, String> {
+    match table.get(name) {
+        Some(Value::Table(t)) => Ok(t),
+        _ => Err(format!("missing [{name}] table")),
+    }
+}
+
+fn string<'a>(value: &'a Value, context: &str) -> Result<&'a str, String> {
+    value.as_str().ok_or_else(|| format!("{context}: expected a string, found {value}"))
+}
+
+fn score(value: &Value, context: &str) -> Result<i32, String> {
+    value.as_integer()
+        .and_then(|n| i32::try_from(n).ok())
+        .ok_or_else(|| format!("{context}: expected a score, found {value}"))
+}
+
+impl Rules {
+    /// The puzzle's own rules, from `day02.rules.toml`.
+    pub fn standard() -> Self {
+        RULES.clone()
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+        text.parse().map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    fn find_move(&self, name: &str, context: &str) -> Result<Move, String> {
+        self.moves.iter().position(|m| m == name)
+            .ok_or_else(|| format!("{context}: unknown move `{name}`"))
+    }
+
+    fn letters<T>(&self, table: &Table, name: &str, parse: impl Fn(&str, &str) -> Result<T, String>)
+        -> Result<HashMap<String, T>, String> {
+        let map = section(table, name)?.iter()
+            .map(|(letter, value)| {
+                let context = format!("[{name}] {letter}");
+                if letter.is_empty() || letter.contains(char::is_whitespace) {
+                    return Err(format!("{context}: letters can't be empty or contain spaces"));
+                }
+                Ok((letter.clone(), parse(string(value, &context)?, &context)?))
+            })
+            .collect::<Result<HashMap<_, _>, String>>()?;
+        if map.is_empty() {
+            return Err(format!("[{name}] has no letters"));
+        }
+        Ok(map)
+    }
+}
+
+impl FromStr for Rules {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let table = s.parse::<Table>().map_err(|e| e.message().to_string())?;
+
+        let moves: Vec<String> = table.get("moves").and_then(Value::as_array)
+            .ok_or("`moves` must be a list of move names")?
+            .iter()
+            .map(|v| string(v, "moves").map(String::from))
+            .collect::<Result<_, _>>()?;
+        if moves.len() < 2 {
+            return Err("there must be at least two moves".into());
+        }
+        if moves.iter().collect::<HashSet<_>>().len() != moves.len() {
+            return Err("move names must be distinct".into());
+        }
+        let mut rules = Rules {
+            beats: vec![vec![false; moves.len()]; moves.len()],
+            move_scoring: vec![0; moves.len()],
+            moves,
+            outcome_scoring: HashMap::new(),
+            opponent_map: HashMap::new(),
+            my_map: HashMap::new(),
+            outcome_map: HashMap::new(),
+        };
+
+        for (name, beaten) in section(&table, "beats")? {
+            let context = format!("[beats] {name}");
+            let winner = rules.find_move(name, &context)?;
+            let beaten = beaten.as_array().ok_or_else(|| format!("{context}: expected a list of moves"))?;
+            for loser in beaten {
+                let loser = rules.find_move(string(loser, &context)?, &context)?;
+                if loser == winner {
+                    return Err(format!("{context}: a move can't beat itself"));
+                }
+                rules.beats[winner][loser] = true;
+            }
+        }
+        for (a, name) in rules.moves.iter().enumerate() {
+            if let Some(b) = (0..rules.moves.len()).find(|&b| rules.beats[a][b] && rules.beats[b][a]) {
+                return Err(format!("{name} and {} can't both beat each other", rules.moves[b]));
+            }
+            if !rules.beats[a].contains(&true) {
+                return Err(format!("{name} doesn't beat anything"));
+            }
+            if !rules.beats.iter().any(|row| row[a]) {
+                return Err(format!("nothing beats {name}"));
+            }
+        }
+
+        let move_scores = section(&table, "move_scores")?;
+        for (m, name) in rules.moves.iter().enumerate() {
+            let value = move_scores.get(name).ok_or_else(|| format!("[move_scores] has no score for {name}"))?;
+            rules.move_scoring[m] = score(value, &format!("[move_scores] {name}"))?;
+        }
+        if let Some(name) = move_scores.keys().find(|k| !rules.moves.contains(k)) {
+            return Err(format!("[move_scores]: unknown move `{name}`"));
+        }
+
+        for (name, value) in section(&table, "outcome_scores")? {
+            let context = format!("[outcome_scores] {name}");
+            let outcome: Outcome = name.parse().map_err(|e| format!("{context}: {e}"))?;
+            rules.outcome_scoring.insert(outcome, score(value, &context)?);
+        }
+        if rules.outcome_scoring.len() != 3 {
+            return Err("[outcome_scores] needs a score for each of win, lose and draw".into());
+        }
+
+        rules.opponent_map = rules.letters(&table, "opponent", |name, context| rules.find_move(name, context))?;
+        rules.my_map = rules.letters(&table, "me", |name, context| rules.find_move(name, context))?;
+        rules.outcome_map = rules.letters(&table, "outcome", |name, context| {
+            name.parse().map_err(|e| format!("{context}: {e}"))
+        })?;
+        let mine: HashSet<&String> = rules.my_map.keys().collect();
+        if mine != rules.outcome_map.keys().collect() {
+            return Err("[me] and [outcome] must use the same letters".into());
+        }
+        Ok(rules)
+    }
+}
+
+fn get_outcome(rules: &Rules, me: Move, opponent: Move) -> Outcome {
+    if rules.beats[me][opponent] {
+        Outcome::Win
+    }
+    else if rules.beats[opponent][me] {
+        Outcome::Lose
+    }
+    else {
+        Outcome::Draw
+    }
+}
+
+/// The move that gets the outcome; if several do, the best scoring one, and
+/// of those the first listed.
+fn get_move(rules: &Rules, me: Outcome, opponent: Move) -> Move {
+    (0..rules.moves.len())
+        .filter(|&m| get_outcome(rules, m, opponent) == me)
+        .min_by_key(|&m| -rules.move_scoring[m])
+        .unwrap()
+}
+
+fn score1(rules: &Rules, turn: &RawTurn) -> i32 {
+    let opponent_move = rules.opponent_map[&turn.opponent];
+    let my_move = rules.my_map[&turn.me];
+
+    let outcome = get_outcome(rules, my_move, opponent_move);
+
+    rules.move_scoring[my_move] + rules.outcome_scoring[&outcome]
+}
+
+fn score2(rules: &Rules, turn: &RawTurn) -> i32 {
+    let opponent_move = rules.opponent_map[&turn.opponent];
+    let my_move = get_move(rules, rules.outcome_map[&turn.me], opponent_move);
+
+    let outcome = get_outcome(rules, my_move, opponent_move);
+
+    rules.move_scoring[my_move] + rules.outcome_scoring[&outcome]
+}
+
+fn part1(input: &[RawTurn]) -> i32 {
+    input.iter().map(|turn| score1(&RULES, turn)).sum::<i32>()
+}
+
+fn part2(input: &[RawTurn]) -> i32 {
+    input.iter().map(|turn| score2(&RULES, turn)).sum::<i32>()
+}
+
+/// Parses a strategy guide written for `rules` and scores it both ways.
+pub fn play(rules: &Rules, input: &str) -> Result<(i32, i32), ParseError> {
+    let turns = lines_with(input, |l| RawTurn::parse_with(rules, l))?;
+    Ok((
+        turns.iter().map(|turn| score1(rules, turn)).sum(),
+        turns.iter().map(|turn| score2(rules, turn)).sum(),
+    ))
+}
+
+/// How an analysis reads the second column of the guide.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reading {
+    /// As my move, like part 1.
+    Moves,
+    /// As the outcome I'm after, like part 2.
+    Outcomes,
+}
+
+/// One way of reading the guide's second column, and how it would score.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mapping {
+    pub reading: Reading,
+    /// Each letter with the move or outcome it stands for, by letter.
+    pub letters: Vec<(String, String)>,
+    pub score: i32,
+    pub wins: usize,
+    pub rounds: usize,
+    /// How many letters mean something other than the rules file says they
+    /// do for this reading.
+    pub distance: usize,
+}
+
+/// Every mapping of the guide's second-column letters onto distinct moves or
+/// distinct outcomes, best scoring first.
+pub struct Analysis {
+    pub mappings: Vec<Mapping>,
+    /// Which of `mappings` the author most likely meant. A guide is written to
+    /// win, but winning every round would give it away, so this is the one
+    /// that wins the most rounds short of all of them. Ties go to the fewest
+    /// letters changed from the rules file, then to the lower, less
+    /// conspicuous, score. `None` only if there are no mappings.
+    pub likely: Option<usize>,
+}
+
+/// Treats the second column of a guide for `rules` as an unknown code and
+/// tries every way of reading it.
+pub fn analyze(rules: &Rules, input: &str) -> Result<Analysis, ParseError> {
+    let turns = lines_with(input, |l| RawTurn::parse_opponent(rules, l))?;
+    let letters: Vec<String> = turns.iter().map(|t| t.me.clone()).sorted().dedup().collect();
+    let rounds = turns.len();
+    let mut mappings: Vec<Mapping> = Vec::new();
+
+    for moves in (0..rules.moves.len()).permutations(letters.len()) {
+        let mut variant = rules.clone();
+        variant.my_map = letters.iter().cloned().zip(moves.iter().copied()).collect();
+        mappings.push(Mapping {
+            reading: Reading::Moves,
+            letters: letters.iter().cloned().zip(moves.iter().map(|&m| rules.moves[m].clone())).collect(),
+            score: turns.iter().map(|turn| score1(&variant, turn)).sum(),
+            wins: turns.iter()
+                .filter(|t| get_outcome(&variant, variant.my_map[&t.me], variant.opponent_map[&t.opponent]) == Outcome::Win)
+                .count(),
+            rounds,
+            distance: letters.iter().zip(&moves).filter(|(l, m)| rules.my_map.get(*l) != Some(*m)).count(),
+        });
+    }
+    for outcomes in Outcome::ALL.into_iter().permutations(letters.len()) {
+        let mut variant = rules.clone();
+        variant.outcome_map = letters.iter().cloned().zip(outcomes.iter().copied()).collect();
+        mappings.push(Mapping {
+            reading: Reading::Outcomes,
+            letters: letters.iter().cloned().zip(outcomes.iter().map(|o| o.name().to_string())).collect(),
+            score: turns.iter().map(|turn| score2(&variant, turn)).sum(),
+            wins: turns.iter().filter(|t| variant.outcome_map[&t.me] == Outcome::Win).count(),
+            rounds,
+            distance: letters.iter().zip(&outcomes).filter(|(l, o)| rules.outcome_map.get(*l) != Some(*o)).count(),
+        });
+    }
+
+    mappings.sort_by_key(|m| (Reverse(m.score), m.distance));
+    let suspicious = |m: &Mapping| m.rounds > 0 && m.wins == m.rounds;
+    let every_one = mappings.iter().all(suspicious);
+    let likely = mappings.iter()
+        .enumerate()
+        .filter(|(_, m)| every_one || !suspicious(m))
+        .min_by_key(|(_, m)| (Reverse(m.wins), m.distance, m.score))
+        .map(|(idx, _)| idx);
+    Ok(Analysis { mappings, likely })
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<RawTurn>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    const DAY: u32 = 2;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        lines(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+}
+
+/// Random input with `size` rounds.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| format!("{} {}\n", rng.pick(&['A', 'B', 'C']), rng.pick(&['X', 'Y', 'Z'])))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_advent_lib::read::test_input;
+
+    #[test]
+    fn day02_test() {
+        let input: Vec<RawTurn> = test_input("A Y\nB X\nC Z\n");
+        assert_eq!(part1(&input), 15);
+        assert_eq!(part2(&input), 12);
+
+        let spock = include_str!("day02.rules.toml")
+            .replace(r#"["rock", "paper", "scissors"]"#, r#"["rock", "paper", "scissors", "lizard", "spock"]"#)
+            .replace(r#"rock = ["scissors"]"#, r#"rock = ["scissors", "lizard"]
+lizard = ["spock", "paper"]
+spock = ["scissors", "rock"]"#)
+            .replace(r#"paper = ["rock"]"#, r#"paper = ["rock", "spock"]"#)
+            .replace(r#"scissors = ["paper"]"#, r#"scissors = ["paper", "lizard"]"#)
+            .replace("scissors = 3\n", "scissors = 3\nlizard = 4\nspock = 5\n")
+            .replace(r#"C = "scissors""#, r#"C = "scissors"
+D = "lizard"
+E = "spock""#);
+        let rules: Rules = spock.parse().unwrap();
+        // Losing to lizard can be paper or spock, and beating rock can be
+        // paper or spock too; spock scores more both times.
+        assert_eq!(play(&rules, "D X\nA Z\n").unwrap(), (1 + 6 + 3, 5 + 5 + 6));
+        assert_eq!(play(&rules, "F X\n").unwrap_err().expected, "A, B, C, D or E");
+
+        let analysis = analyze(&Rules::standard(), "A Y\nB X\nC Z\n").unwrap();
+        assert_eq!(analysis.mappings.len(), 12);
+        assert_eq!((analysis.mappings[0].score, analysis.mappings[0].wins), (24, 3));
+        assert!(analysis.mappings.windows(2).all(|w| w[0].score >= w[1].score));
+        // Short of the give-away 24, every reading wins at most one round, and
+        // both of the rules file's own readings do. Part 2's scores less.
+        let likely = &analysis.mappings[analysis.likely.unwrap()];
+        let letters: Vec<(&str, &str)> = likely.letters.iter().map(|(l, m)| (l.as_str(), m.as_str())).collect();
+        assert_eq!((likely.reading, likely.score, likely.wins, likely.distance), (Reading::Outcomes, 12, 1, 0));
+        assert_eq!(letters, vec![("X", "lose"), ("Y", "draw"), ("Z", "win")]);
+        // A guide that keeps winning, but not every time, reads as moves.
+        let analysis = analyze(&Rules::standard(), "A Y\nB Z\nC X\nA X\n").unwrap();
+        let likely = &analysis.mappings[analysis.likely.unwrap()];
+        assert_eq!((likely.reading, likely.wins, likely.distance), (Reading::Moves, 3, 0));
+
+        let lopsided = spock.replace("spock = [\"scissors\", \"rock\"]", "spock = []");
+        assert_eq!(lopsided.parse::<Rules>().err().unwrap(), "spock doesn't beat anything");
+    }
+}
@@ -0,0 +1,246 @@
+use std::fmt;
+use std::ops::BitAnd;
+use std::str::FromStr;
+use std::vec::Vec;
+use crate::{ParseError, Solution};
+use crate::parse::lines;
+use crate::rng::Rng;
+
+/// A set of item types, with priority `p` stored as bit `p - 1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Items(u64);
+
+impl Items {
+    const ALL: Items = Items((1 << 52) - 1);
+
+    /// The lowest priority in the set, or 0 if it's empty.
+    fn priority(self) -> i32 {
+        if self.0 == 0 { 0 } else { self.0.trailing_zeros() as i32 + 1 }
+    }
+
+    pub fn count(self) -> u32 {
+        self.0.count_ones()
+    }
+}
+
+/// The items as letters, in priority order.
+impl fmt::Display for Items {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for c in ('a'..='z').chain('A'..='Z') {
+            if self.0 & 1 << (priority(c).unwrap() - 1) != 0 {
+                write!(f, "{c}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl BitAnd for Items {
+    type Output = Items;
+    fn bitand(self, other: Items) -> Items {
+        Items(self.0 & other.0)
+    }
+}
+
+impl FromIterator<u32> for Items {
+    fn from_iter<I: IntoIterator<Item = u32>>(priorities: I) -> Self {
+        Items(priorities.into_iter().fold(0, |bits, p| bits | 1 << (p - 1)))
+    }
+}
+
+pub struct Rucksack {
+    len: usize,
+    all: Items,
+    left: Items,
+    right: Items,
+}
+
+impl FromStr for Rucksack {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let priorities: Vec<u32> = s.trim_end().chars().enumerate()
+            .map(|(i, c)| priority(c).ok_or_else(|| ParseError::new(i + 1, c, "an item, a-z or A-Z")))
+            .collect::<Result<_, _>>()?;
+        let len = priorities.len() / 2;
+        Ok(Rucksack {
+            len: priorities.len(),
+            all: priorities.iter().copied().collect(),
+            left: priorities[0..len].iter().copied().collect(),
+            right: priorities[len..].iter().copied().collect(),
+        })
+    }
+}
+
+impl Rucksack {
+    fn common(&self) -> Items {
+        self.left & self.right
+    }
+}
+
+fn priority(c: char) -> Option<u32> {
+    match c {
+        'A'..='Z' => Some((c as u32) - ('A' as u32) + 27),
+        'a'..='z' => Some((c as u32) - ('a' as u32) + 1),
+        _ => None,
+    }
+}
+
+fn part1(input: &[Rucksack]) -> i32 {
+    input.iter()
+        .map(|r| r.common().priority())
+        .sum()
+}
+
+/// The sum of the badge priorities when the elves go in groups of `k`, a
+/// badge being the item every rucksack in the group holds.
+pub fn badge_priorities(input: &[Rucksack], k: usize) -> i32 {
+    input.chunks(k)
+        .map(|group| group.iter().fold(Items::ALL, |badge, r| badge & r.all).priority())
+        .sum()
+}
+
+fn part2(input: &[Rucksack]) -> i32 {
+    badge_priorities(input, 3)
+}
+
+/// Something about a rucksack, or a group of them, that the puzzle says
+/// can't happen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    /// An odd number of items can't be split into two compartments.
+    OddCount(usize),
+    /// The compartments should share exactly one item type.
+    Shared(Items),
+    /// A group should have exactly one badge candidate. `last_line` is the
+    /// group's last rucksack.
+    Badges { last_line: usize, candidates: Items },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// 1-based; the group's first rucksack for `Problem::Badges`.
+    pub line: usize,
+    pub problem: Problem,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.problem {
+            Problem::OddCount(n) => write!(f, "line {}: {n} items won't split into two compartments", self.line),
+            Problem::Shared(items) if items.count() == 0 => write!(f, "line {}: compartments share no items", self.line),
+            Problem::Shared(items) => write!(f, "line {}: compartments share {items}", self.line),
+            Problem::Badges { last_line, candidates } if candidates.count() == 0 =>
+                write!(f, "lines {}-{last_line}: group has no badge", self.line),
+            Problem::Badges { last_line, candidates } =>
+                write!(f, "lines {}-{last_line}: group could have any of {candidates} as its badge", self.line),
+        }
+    }
+}
+
+/// Everything wrong with the input, by line, with the elves grouped in `k`s.
+pub fn diagnose(input: &[Rucksack], k: usize) -> Vec<Diagnostic> {
+    let mut report: Vec<Diagnostic> = Vec::new();
+    for (idx, r) in input.iter().enumerate() {
+        if r.len % 2 != 0 {
+            report.push(Diagnostic { line: idx + 1, problem: Problem::OddCount(r.len) });
+        }
+        else if r.common().count() != 1 {
+            report.push(Diagnostic { line: idx + 1, problem: Problem::Shared(r.common()) });
+        }
+    }
+    for (idx, group) in input.chunks(k).enumerate() {
+        let candidates = group.iter().fold(Items::ALL, |badge, r| badge & r.all);
+        if candidates.count() != 1 {
+            let line = idx * k + 1;
+            report.push(Diagnostic { line, problem: Problem::Badges { last_line: line + group.len() - 1, candidates } });
+        }
+    }
+    report.sort_by_key(|d| d.line);
+    report
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Vec<Rucksack>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    const DAY: u32 = 3;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        lines(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+}
+
+/// Random input with `size` rucksacks, rounded up to whole groups of three.
+/// Each group's three rucksacks draw from disjoint pools of items apart from
+/// their badge, so the badge and each rucksack's misplaced item are unique.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..size.div_ceil(3).max(1) {
+        let mut items: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+        rng.shuffle(&mut items);
+        let badge = items[0];
+        for pool in items[1..].chunks(17) {
+            let (left_pool, right_pool) = pool[1..].split_at(8);
+            let shared = if rng.chance(1, 10) { badge } else { pool[0] };
+            let half = 2 + rng.below(14);
+            let badge_left = rng.chance(1, 2);
+            let mut halves = [vec![shared], vec![shared]];
+            if shared != badge {
+                halves[if badge_left { 0 } else { 1 }].push(badge);
+            }
+            for (side, from) in halves.iter_mut().zip([left_pool, right_pool]) {
+                while side.len() < half {
+                    side.push(*rng.pick(from));
+                }
+                rng.shuffle(side);
+            }
+            out.extend(halves.concat());
+            out.push('\n');
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_advent_lib::read::test_input;
+
+    #[test]
+    fn day03_test() {
+        let input: Vec<Rucksack> = test_input(include_str!("day03.testinput"));
+        assert_eq!(part1(&input), 157);
+        assert_eq!(part2(&input), 70);
+        assert_eq!(badge_priorities(&input, 2), 6 + 17 + 33);
+        assert_eq!(badge_priorities(&input, 6), 0);
+
+        assert!(diagnose(&input, 3).is_empty());
+        let report: Vec<String> = diagnose(&input, 2).iter().map(|d| d.to_string()).collect();
+        assert_eq!(report, vec![
+            "lines 1-2: group could have any of frsFM as its badge",
+            "lines 3-4: group could have any of qvwBT as its badge",
+            "lines 5-6: group could have any of GJZ as its badge",
+        ]);
+        let odd: Vec<Rucksack> = test_input("abcab\nabcd\nabab\n");
+        assert_eq!(diagnose(&odd, 3), vec![
+            Diagnostic { line: 1, problem: Problem::OddCount(5) },
+            Diagnostic { line: 1, problem: Problem::Badges { last_line: 3, candidates: Items(0b11) } },
+            Diagnostic { line: 2, problem: Problem::Shared(Items(0)) },
+            Diagnostic { line: 3, problem: Problem::Shared(Items(0b11)) },
+        ]);
+
+        let err = "abc1".parse::<Rucksack>().err().unwrap();
+        assert_eq!((err.column, err.text.as_str()), (4, "1"));
+    }
+}
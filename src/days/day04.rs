@@ -0,0 +1,414 @@
+use std::cmp::Reverse;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use std::vec::Vec;
+use lazy_static::lazy_static;
+use regex::Regex;
+use crate::{ParseError, Solution};
+use crate::parse::{lines, match_line};
+use crate::rng::Rng;
+
+/// A set of section numbers, kept as sorted ranges that neither overlap nor
+/// touch.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalSet(Vec<RangeInclusive<u32>>);
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        IntervalSet(Vec::new())
+    }
+
+    pub fn ranges(&self) -> &[RangeInclusive<u32>] {
+        &self.0
+    }
+
+    /// How many sections are in the set.
+    pub fn len(&self) -> u64 {
+        self.0.iter().map(|r| (r.end() - r.start()) as u64 + 1).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn contains(&self, section: u32) -> bool {
+        self.0.iter().any(|r| r.contains(&section))
+    }
+
+    pub fn is_superset(&self, other: &IntervalSet) -> bool {
+        other.difference(self).is_empty()
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        self.0.iter().chain(other.0.iter()).cloned().collect()
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut out: Vec<RangeInclusive<u32>> = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.0.len() && j < other.0.len() {
+            let (a, b) = (&self.0[i], &other.0[j]);
+            let (start, end) = (*a.start().max(b.start()), *a.end().min(b.end()));
+            if start <= end {
+                out.push(start..=end);
+            }
+            if a.end() < b.end() { i += 1; } else { j += 1; }
+        }
+        IntervalSet(out)
+    }
+
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut out: Vec<RangeInclusive<u32>> = Vec::new();
+        let mut j = 0;
+        for r in &self.0 {
+            let mut start = *r.start();
+            while j < other.0.len() && other.0[j].end() < &start {
+                j += 1;
+            }
+            let mut k = j;
+            let mut done = false;
+            while k < other.0.len() && other.0[k].start() <= r.end() {
+                let cut = &other.0[k];
+                if *cut.start() > start {
+                    out.push(start..=cut.start() - 1);
+                }
+                match cut.end().checked_add(1) {
+                    Some(next) if next <= *r.end() => start = next,
+                    _ => {
+                        done = true;
+                        break;
+                    },
+                }
+                k += 1;
+            }
+            if !done {
+                out.push(start..=*r.end());
+            }
+        }
+        IntervalSet(out)
+    }
+}
+
+/// Sorts and merges the ranges; empty ones are dropped.
+impl FromIterator<RangeInclusive<u32>> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<u32>>>(ranges: I) -> Self {
+        let mut ranges: Vec<RangeInclusive<u32>> = ranges.into_iter().filter(|r| !r.is_empty()).collect();
+        ranges.sort_by_key(|r| *r.start());
+        let mut merged: Vec<RangeInclusive<u32>> = Vec::new();
+        for r in ranges {
+            match merged.last_mut() {
+                Some(last) if *r.start() <= last.end().saturating_add(1) => {
+                    *last = *last.start() ..= *last.end().max(r.end());
+                },
+                _ => merged.push(r),
+            }
+        }
+        IntervalSet(merged)
+    }
+}
+
+/// Like the input: `2-4,6-8`, or `-` for the empty set.
+impl fmt::Display for IntervalSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.0.is_empty() {
+            return write!(f, "-");
+        }
+        for (idx, r) in self.0.iter().enumerate() {
+            write!(f, "{}{}-{}", if idx > 0 { "," } else { "" }, r.start(), r.end())?;
+        }
+        Ok(())
+    }
+}
+
+/// One line of the input: the sections assigned to each elf in a group,
+/// usually a pair.
+pub struct Group {
+    ranges: Vec<RangeInclusive<u32>>,
+}
+
+impl FromStr for Group {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            static ref RE: Regex = Regex::new(r"^(\d+)-(\d+)$").unwrap();
+        }
+        let mut ranges: Vec<RangeInclusive<u32>> = Vec::new();
+        let mut column = 1;
+        for text in s.split(',') {
+            let at = |e: ParseError| ParseError { column: e.column + column - 1, ..e };
+            let f = match_line(&RE, text, "comma-separated section ranges like 2-4,6-8").map_err(at)?;
+            let frm = f.get::<u32>(1, "a section number").map_err(at)?;
+            let to = f.get::<u32>(2, "a section number").map_err(at)?;
+            if to < frm {
+                return Err(at(f.error(2, &format!("a section number no lower than {frm}"))));
+            }
+            ranges.push(frm ..= to);
+            column += text.chars().count() + 1;
+        }
+        Ok(Group { ranges })
+    }
+}
+
+/// The most elves whose assignments all overlap, and a section they share.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Clique {
+    /// Indices into the group, in order.
+    pub elves: Vec<usize>,
+    pub section: u32,
+}
+
+impl Group {
+    fn elf(&self, idx: usize) -> IntervalSet {
+        IntervalSet(vec![self.ranges[idx].clone()])
+    }
+
+    pub fn covered(&self) -> IntervalSet {
+        self.ranges.iter().cloned().collect()
+    }
+
+    /// Sections between the group's lowest and highest that no elf has.
+    pub fn uncovered(&self) -> IntervalSet {
+        let covered = self.covered();
+        match (covered.0.first(), covered.0.last()) {
+            (Some(first), Some(last)) => IntervalSet(vec![*first.start() ..= *last.end()]).difference(&covered),
+            _ => IntervalSet::new(),
+        }
+    }
+
+    /// Sections that more than one elf has.
+    pub fn shared(&self) -> IntervalSet {
+        let mut seen = IntervalSet::new();
+        let mut shared = IntervalSet::new();
+        for idx in 0..self.ranges.len() {
+            let elf = self.elf(idx);
+            shared = shared.union(&seen.intersection(&elf));
+            seen = seen.union(&elf);
+        }
+        shared
+    }
+
+    /// Ranges on a line overlap all together exactly when they share a
+    /// section, so the biggest clique is found where the most ranges meet.
+    pub fn largest_clique(&self) -> Option<Clique> {
+        self.ranges.iter()
+            .map(|r| *r.start())
+            .map(|section| Clique {
+                elves: (0..self.ranges.len()).filter(|&idx| self.ranges[idx].contains(&section)).collect(),
+                section,
+            })
+            .min_by_key(|c| (Reverse(c.elves.len()), c.section))
+    }
+
+    /// Whether some elf's assignment is entirely within another's.
+    fn is_contained(&self) -> bool {
+        (0..self.ranges.len()).any(|i| (0..self.ranges.len())
+            .any(|j| i != j && self.elf(i).is_superset(&self.elf(j))))
+    }
+
+    fn is_overlapped(&self) -> bool {
+        !self.shared().is_empty()
+    }
+}
+
+/// One elf's assignment, somewhere in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Assignment {
+    /// 1-based.
+    pub line: usize,
+    /// Index into the line's group.
+    pub elf: usize,
+    pub range: RangeInclusive<u32>,
+}
+
+/// A node of a centered interval tree: the assignments that contain
+/// `center`, sorted both ways, and subtrees for those wholly either side.
+struct Node {
+    center: u32,
+    by_start: Vec<usize>,
+    by_end: Vec<usize>,
+    left: Option<Box<Node>>,
+    right: Option<Box<Node>>,
+}
+
+impl Node {
+    fn build(assignments: &[Assignment], mut entries: Vec<usize>) -> Option<Box<Node>> {
+        if entries.is_empty() {
+            return None;
+        }
+        let mut ends: Vec<u32> = entries.iter()
+            .flat_map(|&e| [*assignments[e].range.start(), *assignments[e].range.end()])
+            .collect();
+        let mid = ends.len() / 2;
+        let center = *ends.select_nth_unstable(mid).1;
+        let (left, rest): (Vec<usize>, Vec<usize>) = entries.drain(..)
+            .partition(|&e| *assignments[e].range.end() < center);
+        let (right, mut by_start): (Vec<usize>, Vec<usize>) = rest.into_iter()
+            .partition(|&e| *assignments[e].range.start() > center);
+        by_start.sort_by_key(|&e| *assignments[e].range.start());
+        let mut by_end = by_start.clone();
+        by_end.sort_by_key(|&e| Reverse(*assignments[e].range.end()));
+        Some(Box::new(Node {
+            center,
+            by_start,
+            by_end,
+            left: Node::build(assignments, left),
+            right: Node::build(assignments, right),
+        }))
+    }
+}
+
+/// Every assignment in the input, indexed so that the ones holding a given
+/// section, or overlapping a given range, are found in O(log n + k).
+pub struct SectionIndex {
+    assignments: Vec<Assignment>,
+    tree: Option<Box<Node>>,
+    /// All the assignments, by where they start.
+    by_start: Vec<usize>,
+}
+
+impl SectionIndex {
+    pub fn new(groups: &[Group]) -> Self {
+        let assignments: Vec<Assignment> = groups.iter().enumerate()
+            .flat_map(|(idx, group)| group.ranges.iter().enumerate()
+                .map(move |(elf, range)| Assignment { line: idx + 1, elf, range: range.clone() }))
+            .collect();
+        let mut by_start: Vec<usize> = (0..assignments.len()).collect();
+        by_start.sort_by_key(|&e| *assignments[e].range.start());
+        let tree = Node::build(&assignments, (0..assignments.len()).collect());
+        SectionIndex { assignments, tree, by_start }
+    }
+
+    /// The assignments that include `section`, in no particular order.
+    pub fn stabbing(&self, section: u32) -> Vec<&Assignment> {
+        let mut found: Vec<&Assignment> = Vec::new();
+        let mut node = &self.tree;
+        while let Some(n) = node {
+            if section < n.center {
+                found.extend(n.by_start.iter().map(|&e| &self.assignments[e])
+                    .take_while(|a| *a.range.start() <= section));
+                node = &n.left;
+            }
+            else if section > n.center {
+                found.extend(n.by_end.iter().map(|&e| &self.assignments[e])
+                    .take_while(|a| *a.range.end() >= section));
+                node = &n.right;
+            }
+            else {
+                found.extend(n.by_start.iter().map(|&e| &self.assignments[e]));
+                break;
+            }
+        }
+        found
+    }
+
+    /// The assignments that share at least one section with `range`, in no
+    /// particular order: those holding its first section, and those starting
+    /// later on inside it.
+    pub fn overlapping(&self, range: RangeInclusive<u32>) -> Vec<&Assignment> {
+        if range.is_empty() {
+            return Vec::new();
+        }
+        let mut found = self.stabbing(*range.start());
+        let from = self.by_start.partition_point(|&e| self.assignments[e].range.start() <= range.start());
+        found.extend(self.by_start[from..].iter().map(|&e| &self.assignments[e])
+            .take_while(|a| a.range.start() <= range.end()));
+        found
+    }
+}
+
+fn part1(input: &[Group]) -> usize {
+    input.iter().filter(|p| p.is_contained()).count()
+}
+
+fn part2(input: &[Group]) -> usize {
+    input.iter().filter(|p| p.is_overlapped()).count()
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Vec<Group>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    const DAY: u32 = 4;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        lines(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+}
+
+/// Random input with `size` pairs.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut range = || {
+        let start = rng.range(1..=99);
+        (start, rng.range(start..=99))
+    };
+    (0..size.max(1))
+        .map(|_| {
+            let (a, b) = (range(), range());
+            format!("{}-{},{}-{}\n", a.0, a.1, b.0, b.1)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_advent_lib::read::test_input;
+
+    #[test]
+    fn day04_test() {
+        let input: Vec<Group> = test_input(include_str!("day04.testinput"));
+        assert_eq!(part1(&input), 2);
+        assert_eq!(part2(&input), 4);
+
+        let group: Group = "1-3,9-12,2-5,4-4,11-20".parse().unwrap();
+        assert_eq!(group.covered().to_string(), "1-5,9-20");
+        assert_eq!(group.covered().len(), 17);
+        assert_eq!(group.uncovered().to_string(), "6-8");
+        assert_eq!(group.shared().to_string(), "2-4,11-12");
+        assert_eq!(group.largest_clique(), Some(Clique { elves: vec![0, 2], section: 2 }));
+        let group: Group = "1-10,2-3,3-8".parse().unwrap();
+        assert_eq!(group.largest_clique(), Some(Clique { elves: vec![0, 1, 2], section: 3 }));
+
+        let a: IntervalSet = [0..=4, 10..=u32::MAX].into_iter().collect();
+        let b: IntervalSet = [3..=11, 20..=20].into_iter().collect();
+        assert_eq!(a.union(&b).to_string(), format!("0-{}", u32::MAX));
+        assert_eq!(a.intersection(&b).to_string(), "3-4,10-11,20-20");
+        assert_eq!(a.difference(&b).to_string(), format!("0-2,12-19,21-{}", u32::MAX));
+        assert_eq!(b.difference(&a).to_string(), "5-9");
+
+        let mut groups = input;
+        groups.push("3-5,1-1,9-9,2-9".parse().unwrap());
+        let sorted = |mut found: Vec<&Assignment>| {
+            found.sort_by_key(|a| (a.line, a.elf));
+            found.iter().map(|a| (a.line, a.elf)).collect::<Vec<_>>()
+        };
+        let index = SectionIndex::new(&groups);
+        assert_eq!(sorted(index.stabbing(7)), vec![(1, 1), (3, 0), (3, 1), (4, 0), (4, 1), (6, 1), (7, 3)]);
+        assert_eq!(sorted(index.overlapping(1..=2)), vec![(1, 0), (2, 0), (4, 0), (6, 0), (7, 1), (7, 3)]);
+
+        groups.extend(test_input::<Group>(&generate(&mut Rng::new(4), 40)));
+        let index = SectionIndex::new(&groups);
+        let brute = |range: RangeInclusive<u32>| sorted(index.assignments.iter()
+            .filter(|a| a.range.start() <= range.end() && range.start() <= a.range.end())
+            .collect());
+        for (start, end) in [(0, 0), (1, 1), (5, 5), (7, 7), (3, 40), (50, 60), (99, 200), (0, 200)] {
+            assert_eq!(sorted(index.overlapping(start..=end)), brute(start..=end), "{start}-{end}");
+            assert_eq!(sorted(index.stabbing(start)), brute(start..=start), "{start}");
+        }
+
+        let err = "2-4,8-6".parse::<Group>().err().unwrap();
+        assert_eq!((err.column, err.text.as_str()), (7, "6"));
+    }
+}
@@ -0,0 +1,402 @@
+use std::fmt;
+use std::ops::Range;
+use std::vec::Vec;
+use std::str::FromStr;
+use lazy_static::lazy_static;
+use regex::Regex;
+use crate::{AnswerKind, ParseError, Solution};
+use crate::parse::{match_line, sections};
+use crate::rng::Rng;
+
+pub struct Movement {
+    n: usize,
+    /// 0-based; checked against the diagram once it's known how many stacks
+    /// there are.
+    frm: usize,
+    to: usize,
+    /// Where `n`, `frm` and `to` are on the line, for reporting bad ones.
+    columns: [usize; 3],
+}
+
+enum Token {
+    Crate(String),
+    Label(String),
+}
+
+/// One line of the stack diagram: the crates (`[A]`) or stack labels (`1`)
+/// on it, with the character columns each one spans.
+pub struct DiagramRow(Vec<(Range<usize>, Token)>);
+
+impl FromStr for DiagramRow {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let chars: Vec<char> = s.chars().collect();
+        let mut tokens: Vec<(Range<usize>, Token)> = Vec::new();
+        let mut i = 0;
+        while i < chars.len() {
+            let start = i;
+            match chars[i] {
+                ' ' => {
+                    i += 1;
+                    continue;
+                },
+                '[' => {
+                    let len = chars[i + 1..].iter().position(|c| *c == ']')
+                        .filter(|len| *len > 0 && !chars[i + 1..i + 1 + len].contains(&' '))
+                        .ok_or_else(|| ParseError::new(i + 1, chars[i..].iter().collect::<String>(), "a crate like [A]"))?;
+                    i += len + 2;
+                    tokens.push((start..i, Token::Crate(chars[start + 1..i - 1].iter().collect())));
+                },
+                c if c.is_ascii_digit() => {
+                    while i < chars.len() && chars[i].is_ascii_digit() {
+                        i += 1;
+                    }
+                    tokens.push((start..i, Token::Label(chars[start..i].iter().collect())));
+                },
+                c => return Err(ParseError::new(i + 1, c, "a crate like [A] or a stack number")),
+            }
+        }
+        Ok(DiagramRow(tokens))
+    }
+}
+
+/// The starting stacks, bottom crate first. There are as many as the label
+/// row at the bottom of the diagram numbers.
+pub struct Stacks(Vec<Vec<String>>);
+
+impl Stacks {
+    /// Puts each crate on the stack whose label it sits above.
+    fn from_rows(rows: &[DiagramRow]) -> Result<Self, ParseError> {
+        let Some((labels, crates)) = rows.split_last() else {
+            return Err(ParseError::new(1, "", "a stack diagram").at_line(1));
+        };
+        let label_line = rows.len();
+        let mut spans: Vec<Range<usize>> = Vec::new();
+        for (span, token) in &labels.0 {
+            let expected = (spans.len() + 1).to_string();
+            match token {
+                Token::Label(label) if *label == expected => spans.push(span.clone()),
+                Token::Label(label) => {
+                    return Err(ParseError::new(span.start + 1, label, format!("stack number {expected}")).at_line(label_line));
+                },
+                Token::Crate(_) => {
+                    return Err(ParseError::new(span.start + 1, "[", "the row of stack numbers").at_line(label_line));
+                },
+            }
+        }
+        if spans.is_empty() {
+            return Err(ParseError::new(1, "", "the row of stack numbers").at_line(label_line));
+        }
+
+        let mut stacks: Vec<Vec<String>> = vec![Vec::new(); spans.len()];
+        for (idx, row) in crates.iter().enumerate().rev() {
+            for (span, token) in &row.0 {
+                let error = |text: &str, expected: &str| ParseError::new(span.start + 1, text, expected).at_line(idx + 1);
+                let Token::Crate(name) = token else {
+                    return Err(error("", "a crate like [A]"));
+                };
+                let mut under = spans.iter().enumerate()
+                    .filter(|(_, label)| label.start < span.end && span.start < label.end);
+                let stack = match (under.next(), under.next()) {
+                    (Some((stack, _)), None) => stack,
+                    _ => return Err(error(name, "a crate above exactly one stack number")),
+                };
+                if stacks[stack].len() != label_line - 2 - idx {
+                    return Err(error(name, "a crate sitting on another crate or the floor"));
+                }
+                stacks[stack].push(name.clone());
+            }
+        }
+        Ok(Stacks(stacks))
+    }
+}
+
+impl FromStr for Movement {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            static ref RE: Regex = Regex::new(r"^move (\d+) from (\d+) to (\d+)$").unwrap();
+        }
+        let f = match_line(&RE, s, "a line like `move 1 from 2 to 3`")?;
+        let stack = |group| match f.get::<usize>(group, "a stack number")? {
+            0 => Err(f.error(group, "a stack number from 1")),
+            n => Ok(n - 1),
+        };
+        Ok(Movement {
+            n: f.get(1, "a crate count")?,
+            frm: stack(2)?,
+            to: stack(3)?,
+            columns: [f.column(1), f.column(2), f.column(3)],
+        })
+    }
+}
+
+pub type Input = (Stacks, Vec<Movement>);
+
+/// How a crane moves crates.
+pub trait Crane {
+    /// Takes the top `n` crates off `stack`, which has at least that many,
+    /// and returns them bottom first, the way they'll land.
+    fn lift(&self, stack: &mut Vec<String>, n: usize) -> Vec<String>;
+}
+
+/// Moves crates one at a time, so they land in reverse order (part 1).
+pub struct CrateMover9000;
+
+/// Moves several crates at once, keeping their order (part 2).
+pub struct CrateMover9001;
+
+impl Crane for CrateMover9000 {
+    fn lift(&self, stack: &mut Vec<String>, n: usize) -> Vec<String> {
+        let mut crates = stack.split_off(stack.len() - n);
+        crates.reverse();
+        crates
+    }
+}
+
+impl Crane for CrateMover9001 {
+    fn lift(&self, stack: &mut Vec<String>, n: usize) -> Vec<String> {
+        stack.split_off(stack.len() - n)
+    }
+}
+
+/// A move the crane can't carry out. Steps and stacks are 1-based.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MoveError {
+    /// The move names a stack that isn't there.
+    NoSuchStack { step: usize, stack: usize, stacks: usize },
+    /// The move asks for more crates than its stack has.
+    TooFew { step: usize, needed: usize, stack: usize, had: usize },
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MoveError::NoSuchStack { step, stack, stacks } =>
+                write!(f, "move {step} uses stack {stack}, but there are only {stacks}"),
+            MoveError::TooFew { step, needed, stack, had } =>
+                write!(f, "move {step} needs {needed} crates, stack {stack} has {had}"),
+        }
+    }
+}
+
+/// One move as it happened: stacks are 1-based, and `crates` are the ones
+/// moved, in the order they landed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub step: usize,
+    pub from: usize,
+    pub to: usize,
+    pub crates: Vec<String>,
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "move {}: {} from {} to {}: {}", self.step, self.crates.len(), self.from, self.to, self.crates.join(" "))
+    }
+}
+
+/// The stacks as the crane rearranges them.
+#[derive(Clone)]
+pub struct CrateYard {
+    stacks: Vec<Vec<String>>,
+    steps: usize,
+}
+
+impl CrateYard {
+    pub fn new(stacks: &Stacks) -> Self {
+        CrateYard { stacks: stacks.0.clone(), steps: 0 }
+    }
+
+    /// Carries out the next move, or leaves the stacks alone, and doesn't
+    /// count it, if it names a stack that isn't there or asks for more
+    /// crates than there are.
+    pub fn apply(&mut self, crane: &dyn Crane, mv: &Movement) -> Result<Step, MoveError> {
+        let step = self.steps + 1;
+        let stacks = self.stacks.len();
+        if let Some(stack) = [mv.frm, mv.to].into_iter().find(|s| *s >= stacks) {
+            return Err(MoveError::NoSuchStack { step, stack: stack + 1, stacks });
+        }
+        let had = self.stacks[mv.frm].len();
+        if mv.n > had {
+            return Err(MoveError::TooFew { step, needed: mv.n, stack: mv.frm + 1, had });
+        }
+        let crates = crane.lift(&mut self.stacks[mv.frm], mv.n);
+        self.stacks[mv.to].extend(crates.iter().cloned());
+        self.steps = step;
+        Ok(Step { step, from: mv.frm + 1, to: mv.to + 1, crates })
+    }
+
+    /// Carries out every move, stopping at the first bad one, and returns
+    /// what each did.
+    pub fn run(&mut self, crane: &dyn Crane, moves: &[Movement]) -> Result<Vec<Step>, MoveError> {
+        moves.iter().map(|mv| self.apply(crane, mv)).collect()
+    }
+
+    /// The crate on top of each stack, run together.
+    pub fn tops(&self) -> String {
+        self.stacks.iter().filter_map(|s| s.last().cloned()).collect::<String>()
+    }
+
+    /// Draws the stacks the way the puzzle input does, each column wide
+    /// enough for its longest crate.
+    pub fn render(&self) -> String {
+        let widths: Vec<usize> = self.stacks.iter().enumerate()
+            .map(|(idx, stack)| stack.iter().map(|c| c.chars().count() + 2)
+                .chain([3, (idx + 1).to_string().len()])
+                .max()
+                .unwrap())
+            .collect();
+        let centered = |text: &str, width: usize| {
+            let pad = width - text.chars().count();
+            format!("{}{text}{}", " ".repeat(pad / 2), " ".repeat(pad - pad / 2))
+        };
+        let height = self.stacks.iter().map(|s| s.len()).max().unwrap_or(0);
+        let mut out = String::new();
+        for level in (0..height).rev() {
+            let row = self.stacks.iter().zip(&widths)
+                .map(|(stack, w)| stack.get(level).map_or(" ".repeat(*w), |c| centered(&format!("[{c}]"), *w)))
+                .collect::<Vec<String>>()
+                .join(" ");
+            out += row.trim_end();
+            out.push('\n');
+        }
+        let labels = widths.iter().enumerate()
+            .map(|(idx, w)| centered(&(idx + 1).to_string(), *w))
+            .collect::<Vec<String>>()
+            .join(" ");
+        out += labels.trim_end();
+        out.push('\n');
+        out
+    }
+}
+
+fn part(input: &Input, part: i32) -> String {
+    let crane: &dyn Crane = if part == 1 { &CrateMover9000 } else { &CrateMover9001 };
+    let mut yard = CrateYard::new(&input.0);
+    // `parse` has already tried every move.
+    yard.run(crane, &input.1).unwrap();
+    yard.tops()
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Input;
+    type Answer1 = String;
+    type Answer2 = String;
+
+    const DAY: u32 = 5;
+    const KIND1: AnswerKind = AnswerKind::String;
+    const KIND2: AnswerKind = AnswerKind::String;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        let (rows, moves): (Vec<DiagramRow>, Vec<Movement>) = sections(input)?;
+        let stacks = Stacks::from_rows(&rows)?;
+        let count = stacks.0.len();
+        for (idx, mv) in moves.iter().enumerate() {
+            for (stack, column) in [mv.frm, mv.to].into_iter().zip(&mv.columns[1..]) {
+                if stack >= count {
+                    let expected = format!("a stack number from 1 to {count}");
+                    return Err(ParseError::new(*column, (stack + 1).to_string(), expected).at_line(rows.len() + 2 + idx));
+                }
+            }
+        }
+        let mut yard = CrateYard::new(&stacks);
+        for (idx, mv) in moves.iter().enumerate() {
+            // The stack numbers were checked above, so only running short is left.
+            if let Err(MoveError::TooFew { stack, had, .. }) = yard.apply(&CrateMover9001, mv) {
+                let expected = format!("at most {had} crates, all that stack {stack} has by then");
+                return Err(ParseError::new(mv.columns[0], mv.n.to_string(), expected).at_line(rows.len() + 2 + idx));
+            }
+        }
+        Ok((stacks, moves))
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        part(input, 1)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        part(input, 2)
+    }
+}
+
+/// Random input with `size` moves, all of which take crates from a stack
+/// that has enough of them.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut heights: Vec<usize> = (0..9).map(|_| rng.below(9)).collect();
+    heights[rng.below(9)] += 1;
+    let mut out = String::new();
+    for level in (0..*heights.iter().max().unwrap()).rev() {
+        let row: Vec<String> = heights.iter()
+            .map(|h| if *h > level { format!("[{}]", (b'A' + rng.below(26) as u8) as char) } else { "   ".into() })
+            .collect();
+        out += &row.join(" ");
+        out.push('\n');
+    }
+    out += " 1   2   3   4   5   6   7   8   9 \n\n";
+    for _ in 0..size.max(1) {
+        let nonempty: Vec<usize> = (0..9).filter(|i| heights[*i] > 0).collect();
+        let frm = *rng.pick(&nonempty);
+        let to = (frm + 1 + rng.below(8)) % 9;
+        let n = 1 + rng.below(heights[frm].min(10));
+        heights[frm] -= n;
+        heights[to] += n;
+        out += &format!("move {n} from {} to {}\n", frm + 1, to + 1);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day05_test() {
+        let input: Input = Day05.parse(include_str!("day05.testinput")).unwrap();
+        assert_eq!(part(&input, 1), "CMZ".to_string());
+        assert_eq!(part(&input, 2), "MCD".to_string());
+
+        let wide = "                                    [Kx]
+[A]                                 [Jy]
+[B] [C] [D] [E] [F] [G] [H] [I] [J] [Ky]
+ 1   2   3   4   5   6   7   8   9   10 
+
+move 2 from 10 to 1
+move 1 from 1 to 10
+";
+        let input: Input = Day05.parse(wide).unwrap();
+        assert_eq!(input.0.0[9], vec!["Ky", "Jy", "Kx"]);
+        assert_eq!(part(&input, 1), "KxCDEFGHIJJy");
+        assert_eq!(part(&input, 2), "JyCDEFGHIJKx");
+
+        let mut yard = CrateYard::new(&input.0);
+        let steps = yard.run(&CrateMover9000, &input.1).unwrap();
+        assert_eq!(steps[0].to_string(), "move 1: 2 from 10 to 1: Kx Jy");
+        let redrawn: Input = Day05.parse(&(yard.render() + "\n")).unwrap();
+        assert_eq!(redrawn.0.0, yard.stacks);
+        let example: Input = Day05.parse(include_str!("day05.testinput")).unwrap();
+        assert_eq!(CrateYard::new(&example.0).render(), "    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3\n");
+
+        let short: Input = Day05.parse("[A]\n 1   2\n\nmove 1 from 1 to 2\n").unwrap();
+        let mut yard = CrateYard::new(&short.0);
+        assert_eq!(yard.apply(&CrateMover9000, &short.1[0]).unwrap().crates, vec!["A"]);
+        let mv = Movement { n: 2, frm: 1, to: 0, columns: [6, 13, 18] };
+        assert_eq!(yard.apply(&CrateMover9000, &mv).unwrap_err().to_string(), "move 2 needs 2 crates, stack 2 has 1");
+        let mv = Movement { n: 1, frm: 11, to: 0, columns: [6, 13, 19] };
+        assert_eq!(yard.apply(&CrateMover9000, &mv).unwrap_err(), MoveError::NoSuchStack { step: 2, stack: 12, stacks: 2 });
+        let mv: Movement = "move 1 from 1 to 12".parse().unwrap();
+        assert_eq!(yard.apply(&CrateMover9000, &mv).unwrap_err().to_string(), "move 2 uses stack 12, but there are only 2");
+        // None of the rejected moves counted.
+        let mv = Movement { n: 1, frm: 1, to: 0, columns: [6, 13, 18] };
+        assert_eq!(yard.apply(&CrateMover9000, &mv).unwrap().step, 2);
+        let err = Day05.parse("[A]\n 1   2\n\nmove 2 from 1 to 2\n").err().unwrap();
+        assert_eq!((err.line, err.column, err.text.as_str()), (4, 6, "2"));
+
+        let err = Day05.parse(&wide.replace("to 1\n", "to 11\n")).err().unwrap();
+        assert_eq!((err.line, err.column, err.expected.as_str()), (6, 19, "a stack number from 1 to 10"));
+        let err = Day05.parse(&wide.replace("[A]  ", "  [A]")).err().unwrap();
+        assert_eq!((err.line, err.column), (2, 3));
+    }
+}
@@ -0,0 +1,235 @@
+use std::collections::VecDeque;
+use std::io::{self, BufReader, Read};
+use std::ops::Range;
+use std::vec::Vec;
+use crate::{ParseError, Solution};
+use crate::parse::lines_with;
+use crate::rng::Rng;
+
+/// The last `size` bytes of a datastream, with a count of each byte value so
+/// that sliding along by one is O(1).
+struct Window {
+    size: usize,
+    recent: VecDeque<u8>,
+    counts: [usize; 256],
+    distinct: usize,
+}
+
+impl Window {
+    fn new(size: usize) -> Self {
+        Window { size, recent: VecDeque::with_capacity(size + 1), counts: [0; 256], distinct: 0 }
+    }
+
+    /// Slides the window on by `b`, and says whether it now holds `size`
+    /// different bytes.
+    fn push(&mut self, b: u8) -> bool {
+        self.recent.push_back(b);
+        self.counts[b as usize] += 1;
+        if self.counts[b as usize] == 1 {
+            self.distinct += 1;
+        }
+        if self.recent.len() > self.size {
+            let old = self.recent.pop_front().unwrap() as usize;
+            self.counts[old] -= 1;
+            if self.counts[old] == 0 {
+                self.distinct -= 1;
+            }
+        }
+        self.distinct == self.size
+    }
+}
+
+/// How many bytes have to be read before the first `n_distinct` different
+/// ones in a row, if there are ever that many.
+fn find_marker(input: &str, n_distinct: usize) -> Option<usize> {
+    let mut window = Window::new(n_distinct);
+    input.bytes().position(|b| window.push(b)).map(|i| i + 1)
+}
+
+/// `find_marker` for a datastream too big to hold in memory. Reading stops at
+/// the end of the first line.
+pub fn stream_marker<R: Read>(reader: R, n_distinct: usize) -> io::Result<Option<usize>> {
+    let mut window = Window::new(n_distinct);
+    for (i, b) in BufReader::new(reader).bytes().enumerate() {
+        match b? {
+            b'\n' | b'\r' => break,
+            b if window.push(b) => return Ok(Some(i + 1)),
+            _ => {},
+        }
+    }
+    Ok(None)
+}
+
+/// One of the messages a datastream is split into: a marker and everything
+/// up to the next marker that doesn't overlap it. Both are byte offsets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    pub marker: Range<usize>,
+    pub body: Range<usize>,
+}
+
+/// Something `scan_markers` has come across, for markers `size` bytes long.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Found {
+    /// A marker, complete once `end` bytes have been read. Markers can overlap.
+    Marker { size: usize, end: usize },
+    /// A message, once the next one starts or the datastream ends.
+    Message { size: usize, message: Message },
+}
+
+/// Looks for markers of each size in `sizes` in one pass, reporting each one
+/// and each message to `found` as soon as it's known, so that nothing but the
+/// windows is kept. Reading stops at the end of the first line; returns how
+/// many bytes that was.
+pub fn scan_markers<R: Read>(reader: R, sizes: &[usize], mut found: impl FnMut(Found)) -> io::Result<usize> {
+    let mut windows: Vec<Window> = sizes.iter().map(|&size| Window::new(size)).collect();
+    let mut open: Vec<Option<Message>> = vec![None; sizes.len()];
+    let mut len = 0;
+    for b in BufReader::new(reader).bytes() {
+        let b = b?;
+        if b == b'\n' || b == b'\r' {
+            break;
+        }
+        len += 1;
+        for ((window, open), &size) in windows.iter_mut().zip(open.iter_mut()).zip(sizes) {
+            if !window.push(b) {
+                continue;
+            }
+            found(Found::Marker { size, end: len });
+            let start = len - size;
+            match open.take() {
+                Some(msg) if start < msg.marker.end => {
+                    *open = Some(msg);
+                    continue;
+                },
+                Some(mut msg) => {
+                    msg.body.end = start;
+                    found(Found::Message { size, message: msg });
+                },
+                None => {},
+            }
+            *open = Some(Message { marker: start..len, body: len..len });
+        }
+    }
+    for (open, &size) in open.into_iter().zip(sizes) {
+        if let Some(mut msg) = open {
+            msg.body.end = len;
+            found(Found::Message { size, message: msg });
+        }
+    }
+    Ok(len)
+}
+
+fn part1(input: &[String]) -> usize {
+    // `parse` has already made sure there's a marker.
+    find_marker(&input[0], 4).unwrap()
+}
+
+fn part2(input: &[String]) -> usize {
+    find_marker(&input[0], 14).unwrap()
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = Vec<String>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    const DAY: u32 = 6;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        let input: Vec<String> = lines_with(input, |l| Ok(l.to_string()))?;
+        if input.is_empty() {
+            return Err(ParseError::new(1, "", "a datastream").at_line(1));
+        }
+        for (n_distinct, expected) in [(4, "a start-of-packet marker"), (14, "a start-of-message marker")] {
+            if find_marker(&input[0], n_distinct).is_none() {
+                return Err(ParseError::new(input[0].chars().count() + 1, "", expected).at_line(1));
+            }
+        }
+        Ok(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+}
+
+/// Random input: a datastream of `size` characters. Before the planted
+/// start-of-message marker it uses only three letters plus one planted
+/// start-of-packet marker, so no earlier start-of-message marker can occur.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let len = size.max(42);
+    let packet = rng.below(len / 3);
+    let message = len / 3 + 4 + rng.below(len - len / 3 - 4 - 14 + 1);
+    let mut letters: Vec<char> = ('a'..='z').collect();
+    rng.shuffle(&mut letters);
+    let mut out: String = (0..len)
+        .map(|i| match i {
+            _ if (packet..packet + 4).contains(&i) => letters[3 + i - packet],
+            _ if (message..message + 14).contains(&i) => letters[i - message],
+            _ if i < message => letters[rng.below(3)],
+            _ => letters[rng.below(26)],
+        })
+        .collect();
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_advent_lib::read::test_input;
+
+    #[test]
+    fn day06_test() {
+        let input: Vec<String> = test_input("mjqjpqmgbljsphdztnvjfqwrcgsmlb\n");
+        assert_eq!(part1(&input), 7);
+        assert_eq!(part2(&input), 19);
+        let input: Vec<String> = test_input("bvwbjplbgvbhsrlpgdmjqwftvncz\n");
+        assert_eq!(part1(&input), 5);
+        assert_eq!(part2(&input), 23);
+        let input: Vec<String> = test_input("nppdvjthqldpwncqszvftbrmjlhg\n");
+        assert_eq!(part1(&input), 6);
+        assert_eq!(part2(&input), 23);
+        let input: Vec<String> = test_input("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg\n");
+        assert_eq!(part1(&input), 10);
+        assert_eq!(part2(&input), 29);
+        let input: Vec<String> = test_input("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw\n");
+        assert_eq!(part1(&input), 11);
+        assert_eq!(part2(&input), 26);
+
+        assert_eq!(find_marker("abcabcabcd", 4), Some(10));
+        assert_eq!(find_marker("abcabcabc", 4), None);
+        assert_eq!(stream_marker("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw\n".as_bytes(), 14).unwrap(), Some(26));
+        assert_eq!(stream_marker("abcabc\nd".as_bytes(), 4).unwrap(), None);
+
+        let mut found: Vec<Found> = Vec::new();
+        let len = scan_markers("aabcdeefgaahijk\nabc".as_bytes(), &[3, 4], |f| found.push(f)).unwrap();
+        assert_eq!(len, 15);
+        let ends: Vec<usize> = found.iter()
+            .filter_map(|f| match f { Found::Marker { size: 3, end } => Some(*end), _ => None })
+            .collect();
+        assert_eq!(ends, vec![4, 5, 6, 9, 10, 13, 14, 15]);
+        let messages: Vec<Message> = found.iter()
+            .filter_map(|f| match f { Found::Message { size: 3, message } => Some(message.clone()), _ => None })
+            .collect();
+        assert_eq!(messages, vec![
+            Message { marker: 1..4, body: 4..6 },
+            Message { marker: 6..9, body: 9..10 },
+            Message { marker: 10..13, body: 13..15 },
+        ]);
+        // Messages are reported as they end, not all at once at the end.
+        let first = found.iter().position(|f| matches!(f, Found::Message { size: 3, .. })).unwrap();
+        assert_eq!(found[first - 1], Found::Marker { size: 3, end: 9 });
+        assert!(found.contains(&Found::Message { size: 4, message: Message { marker: 1..5, body: 5..6 } }));
+
+        let err = Day06.parse("abcdabcd\n").err().unwrap();
+        assert_eq!((err.column, err.expected.as_str()), (9, "a start-of-message marker"));
+    }
+}
@@ -0,0 +1,686 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+use std::vec::Vec;
+use json::JsonValue;
+use lazy_static::lazy_static;
+use regex::Regex;
+use crate::{ParseError, Solution};
+use crate::parse::{lines, try_match};
+use crate::rng::Rng;
+
+pub struct File {
+    pub size: usize,
+    /// Where it was first listed, 1-based.
+    line: usize,
+}
+
+pub struct DirNode {
+    pub name: String,
+    parent: Option<usize>,
+    /// Indexes into the tree's nodes.
+    pub dirs: BTreeMap<String, usize>,
+    pub files: BTreeMap<String, File>,
+}
+
+/// The filesystem a terminal transcript explores, as far as its listings
+/// show it. Directories are numbered in the order they were first listed,
+/// starting with the root, `DirTree::ROOT`.
+pub struct DirTree {
+    nodes: Vec<DirNode>,
+    /// Each directory's total size, everything under it included.
+    sizes: Vec<usize>,
+}
+
+/// A file or directory found by `DirTree::find`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub path: String,
+    pub size: usize,
+    pub is_dir: bool,
+}
+
+impl DirTree {
+    pub const ROOT: usize = 0;
+
+    fn new() -> Self {
+        let root = DirNode { name: "/".into(), parent: None, dirs: BTreeMap::new(), files: BTreeMap::new() };
+        DirTree { nodes: vec![root], sizes: Vec::new() }
+    }
+
+    /// Replays a transcript, starting in the root. `cd /` goes back to the
+    /// root and `cd ..` there stays put, as in a shell. A directory can be
+    /// listed more than once, but the listings mustn't contradict each other,
+    /// and `cd` can only go into a directory that has been listed.
+    pub fn from_transcript(input: &[Input]) -> Result<Self, ParseError> {
+        let mut tree = DirTree::new();
+        let mut cwd = DirTree::ROOT;
+        let mut listing = false;
+        for (idx, row) in input.iter().enumerate() {
+            let line = idx + 1;
+            match row {
+                Input::Chdir(chdir) => {
+                    listing = false;
+                    cwd = match chdir.dir.as_str() {
+                        "/" => DirTree::ROOT,
+                        ".." => tree.nodes[cwd].parent.unwrap_or(DirTree::ROOT),
+                        name => match tree.nodes[cwd].dirs.get(name) {
+                            Some(dir) => *dir,
+                            None => {
+                                let expected = format!("a directory listed in {}", tree.path(cwd));
+                                return Err(ParseError::new(chdir.column, name, expected).at_line(line));
+                            },
+                        },
+                    };
+                },
+                Input::List => listing = true,
+                Input::File(_) | Input::Dir(_) if !listing => {
+                    return Err(ParseError::new(1, "", "a command, as `ls` hasn't been run here").at_line(line));
+                },
+                Input::File(ifile) => tree.add_file(cwd, ifile, line)?,
+                Input::Dir(idir) => tree.add_dir(cwd, idir, line)?,
+            }
+        }
+        tree.total_sizes();
+        Ok(tree)
+    }
+
+    fn add_file(&mut self, cwd: usize, ifile: &InputFile, line: usize) -> Result<(), ParseError> {
+        check_name(&ifile.name, ifile.name_column, line)?;
+        if self.nodes[cwd].dirs.contains_key(&ifile.name) {
+            let expected = format!("a name that isn't a directory in {}", self.path(cwd));
+            return Err(ParseError::new(ifile.name_column, ifile.name.as_str(), expected).at_line(line));
+        }
+        match self.nodes[cwd].files.get(&ifile.name) {
+            Some(file) if file.size != ifile.size => {
+                let expected = format!("{}, the size listed on line {}", file.size, file.line);
+                Err(ParseError::new(1, ifile.size.to_string(), expected).at_line(line))
+            },
+            Some(_) => Ok(()),
+            None => {
+                self.nodes[cwd].files.insert(ifile.name.clone(), File { size: ifile.size, line });
+                Ok(())
+            },
+        }
+    }
+
+    fn add_dir(&mut self, cwd: usize, idir: &InputDir, line: usize) -> Result<(), ParseError> {
+        check_name(&idir.name, 5, line)?;
+        if self.nodes[cwd].files.contains_key(&idir.name) {
+            let expected = format!("a name that isn't a file in {}", self.path(cwd));
+            return Err(ParseError::new(5, idir.name.as_str(), expected).at_line(line));
+        }
+        if !self.nodes[cwd].dirs.contains_key(&idir.name) {
+            let dir = DirNode { name: idir.name.clone(), parent: Some(cwd), dirs: BTreeMap::new(), files: BTreeMap::new() };
+            self.nodes.push(dir);
+            let idx = self.nodes.len() - 1;
+            self.nodes[cwd].dirs.insert(idir.name.clone(), idx);
+        }
+        Ok(())
+    }
+
+    /// Fills in `sizes`. Every directory comes after its parent in `nodes`,
+    /// so going backwards finishes each one before adding it to its parent.
+    fn total_sizes(&mut self) {
+        self.sizes = self.nodes.iter().map(|d| d.files.values().map(|f| f.size).sum()).collect();
+        for dir in (1..self.nodes.len()).rev() {
+            let parent = self.nodes[dir].parent.unwrap();
+            self.sizes[parent] += self.sizes[dir];
+        }
+    }
+
+    pub fn get(&self, dir: usize) -> &DirNode {
+        &self.nodes[dir]
+    }
+
+    /// Every directory, the root first.
+    pub fn dirs(&self) -> impl Iterator<Item = usize> {
+        0..self.nodes.len()
+    }
+
+    /// The full path of a directory, like `/a/e`.
+    pub fn path(&self, dir: usize) -> String {
+        let mut names: Vec<&str> = Vec::new();
+        let mut dir = dir;
+        while let Some(parent) = self.nodes[dir].parent {
+            names.push(&self.nodes[dir].name);
+            dir = parent;
+        }
+        names.reverse();
+        format!("/{}", names.join("/"))
+    }
+
+    pub fn size(&self, dir: usize) -> usize {
+        self.sizes[dir]
+    }
+
+    /// The directories in the order `du` lists them, each after everything
+    /// in it, with how deep each is (the root is 0). With `max_depth`, only
+    /// those that deep or less.
+    pub fn du(&self, max_depth: Option<usize>) -> Vec<(usize, usize)> {
+        let mut order: Vec<(usize, usize)> = Vec::new();
+        let mut stack = vec![(DirTree::ROOT, 0)];
+        while let Some((dir, depth)) = stack.pop() {
+            order.push((dir, depth));
+            stack.extend(self.nodes[dir].dirs.values().map(|c| (*c, depth + 1)));
+        }
+        order.reverse();
+        order.retain(|(_, depth)| max_depth.is_none_or(|max| *depth <= max));
+        order
+    }
+
+    /// Every file and directory that `matches` accepts, each directory before
+    /// what's in it and names in order.
+    pub fn find(&self, matches: impl Fn(&Entry) -> bool) -> Vec<Entry> {
+        let mut found: Vec<Entry> = Vec::new();
+        let mut stack = vec![DirTree::ROOT];
+        while let Some(dir) = stack.pop() {
+            let path = self.path(dir);
+            let entry = Entry { path: path.clone(), size: self.sizes[dir], is_dir: true };
+            if matches(&entry) {
+                found.push(entry);
+            }
+            for (name, file) in &self.nodes[dir].files {
+                let path = if dir == DirTree::ROOT { format!("/{name}") } else { format!("{path}/{name}") };
+                let entry = Entry { path, size: file.size, is_dir: false };
+                if matches(&entry) {
+                    found.push(entry);
+                }
+            }
+            stack.extend(self.nodes[dir].dirs.values().rev());
+        }
+        found
+    }
+
+    /// The `n` biggest directories, biggest first.
+    pub fn largest(&self, n: usize) -> Vec<usize> {
+        let mut dirs: Vec<usize> = self.dirs().collect();
+        dirs.sort_by_key(|d| std::cmp::Reverse(self.sizes[*d]));
+        dirs.truncate(n);
+        dirs
+    }
+
+    /// The directories to delete to have `needed` bytes free on a disk of
+    /// `disk` bytes: none inside another, and between them as small as they
+    /// can be. The root counts, as deleting everything in it. Empty if
+    /// there's already room; `None` if the tree doesn't fit on the disk or
+    /// even deleting everything isn't enough.
+    pub fn to_free(&self, disk: usize, needed: usize) -> Option<Vec<usize>> {
+        let free = disk.checked_sub(self.sizes[DirTree::ROOT])?;
+        let short = needed.saturating_sub(free);
+        if short == 0 {
+            return Some(Vec::new());
+        }
+        let (pre, end) = self.preorder();
+        let mut best: Option<usize> = None;
+        self.sweep(DirTree::ROOT, &Totals::zero(short), &mut |dir, after| {
+            let size = self.sizes[dir];
+            let rest = if size >= short { Some(0) } else { after.first_from(short - size) };
+            if let Some(rest) = rest {
+                best = Some(best.map_or(size + rest, |b| b.min(size + rest)));
+            }
+        });
+
+        // Pick the directories one at a time, each time the first in
+        // preorder from which the rest of the total can still be made.
+        let mut left = best?;
+        let mut start = 0;
+        let mut dirs = Vec::new();
+        while left > 0 {
+            let mut first = None;
+            self.sweep(DirTree::ROOT, &Totals::zero(left + 1), &mut |dir, after| {
+                let size = self.sizes[dir];
+                if pre[dir] >= start && size > 0 && size <= left && after.has(left - size) {
+                    first = Some(dir);
+                }
+            });
+            let dir = first.unwrap();
+            dirs.push(dir);
+            left -= self.sizes[dir];
+            start = end[dir];
+        }
+        Some(dirs)
+    }
+
+    /// Each directory's position in preorder, and the position just past
+    /// everything in it.
+    fn preorder(&self) -> (Vec<usize>, Vec<usize>) {
+        fn visit(tree: &DirTree, dir: usize, pre: &mut [usize], end: &mut [usize], next: &mut usize) {
+            pre[dir] = *next;
+            *next += 1;
+            for child in tree.nodes[dir].dirs.values() {
+                visit(tree, *child, pre, end, next);
+            }
+            end[dir] = *next;
+        }
+        let (mut pre, mut end) = (vec![0; self.nodes.len()], vec![0; self.nodes.len()]);
+        visit(self, DirTree::ROOT, &mut pre, &mut end, &mut 0);
+        (pre, end)
+    }
+
+    /// The totals that deleting directories from `dir` on, in preorder, can
+    /// come to when `after` are those for everything past `dir`'s subtree.
+    /// Goes backwards, calling `visit` with each directory and the totals
+    /// past its subtree, so the last call is for `dir` itself.
+    fn sweep(&self, dir: usize, after: &Totals, visit: &mut impl FnMut(usize, &Totals)) -> Totals {
+        let mut totals = after.clone();
+        for child in self.nodes[dir].dirs.values().rev() {
+            totals = self.sweep(*child, &totals, visit);
+        }
+        totals.add_shifted(after, self.sizes[dir]);
+        visit(dir, after);
+        totals
+    }
+}
+
+/// Which totals below some width can be made, a bit each.
+#[derive(Clone)]
+struct Totals {
+    bits: Vec<u64>,
+    width: usize,
+}
+
+impl Totals {
+    /// Just 0.
+    fn zero(width: usize) -> Self {
+        let mut bits = vec![0; width.div_ceil(64)];
+        bits[0] = 1;
+        Totals { bits, width }
+    }
+
+    fn has(&self, n: usize) -> bool {
+        n < self.width && self.bits[n / 64] & (1 << (n % 64)) != 0
+    }
+
+    /// The smallest total of at least `n`.
+    fn first_from(&self, n: usize) -> Option<usize> {
+        if n >= self.width {
+            return None;
+        }
+        let masked = self.bits[n / 64] & (u64::MAX << (n % 64));
+        std::iter::once(masked)
+            .chain(self.bits[n / 64 + 1..].iter().copied())
+            .enumerate()
+            .find(|(_, word)| *word != 0)
+            .map(|(idx, word)| (n / 64 + idx) * 64 + word.trailing_zeros() as usize)
+    }
+
+    /// Adds `by` to each of `other`'s totals, keeping those that fit.
+    fn add_shifted(&mut self, other: &Totals, by: usize) {
+        let (words, shift) = (by / 64, by % 64);
+        for idx in (words..self.bits.len()).rev() {
+            let low = other.bits[idx - words] << shift;
+            let high = if shift > 0 && idx > words { other.bits[idx - words - 1] >> (64 - shift) } else { 0 };
+            self.bits[idx] |= low | high;
+        }
+        if !self.width.is_multiple_of(64) {
+            *self.bits.last_mut().unwrap() &= (1 << (self.width % 64)) - 1;
+        }
+    }
+}
+
+/// The start of every `to_script` script: `d PATH` makes a directory and
+/// `f SIZE PATH` a file, PATH being relative to the new directory.
+const SCRIPT_HEADER: &str = r#"#!/bin/sh
+set -e
+root=$(realpath "$(mktemp -d)")
+inside() {
+    case "$(realpath -m -- "$root/$1")" in
+        "$root"/*) ;;
+        *) echo "refusing to write outside $root: $1" >&2; exit 1 ;;
+    esac
+}
+d() { inside "$1"; mkdir -- "$root/$1"; }
+f() { inside "$2"; truncate -s "$1" -- "$root/$2"; }
+"#;
+
+/// What's directly in a directory, by name, with each file's size.
+enum Child {
+    Dir(usize),
+    File(usize),
+}
+
+impl DirTree {
+    fn children(&self, dir: usize) -> Vec<(&str, Child)> {
+        let node = &self.nodes[dir];
+        let mut children: Vec<(&str, Child)> = node.dirs.iter().map(|(name, d)| (name.as_str(), Child::Dir(*d)))
+            .chain(node.files.iter().map(|(name, file)| (name.as_str(), Child::File(file.size))))
+            .collect();
+        children.sort_by_key(|(name, _)| *name);
+        children
+    }
+
+    fn fmt_children(&self, f: &mut fmt::Formatter, dir: usize, prefix: &str) -> fmt::Result {
+        let children = self.children(dir);
+        for (idx, (name, child)) in children.iter().enumerate() {
+            let last = idx == children.len() - 1;
+            let size = match child {
+                Child::Dir(d) => self.sizes[*d],
+                Child::File(size) => *size,
+            };
+            writeln!(f, "{prefix}{}[{size:>11}]  {name}", if last { "└── " } else { "├── " })?;
+            if let Child::Dir(d) = child {
+                self.fmt_children(f, *d, &format!("{prefix}{}", if last { "    " } else { "│   " }))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The tree in the shape `tree -J` gives it, but with directories' sizes
+    /// including what's in them.
+    pub fn to_json(&self) -> JsonValue {
+        self.dir_json(DirTree::ROOT)
+    }
+
+    fn dir_json(&self, dir: usize) -> JsonValue {
+        let contents: Vec<JsonValue> = self.children(dir).into_iter()
+            .map(|(name, child)| match child {
+                Child::Dir(d) => self.dir_json(d),
+                Child::File(size) => json::object! { type: "file", name: name, size: size },
+            })
+            .collect();
+        json::object! {
+            type: "directory",
+            name: self.nodes[dir].name.as_str(),
+            size: self.sizes[dir],
+            contents: contents,
+        }
+    }
+
+    /// A shell script that recreates the tree in a new temporary directory,
+    /// with sparse files of the listed sizes, and prints where. It stops
+    /// before writing to any path that would end up outside that directory.
+    /// Needs GNU `mktemp`, `realpath` and `truncate`.
+    pub fn to_script(&self) -> String {
+        let quote = |path: &str| format!("'{}'", path.trim_start_matches('/').replace('\'', "'\\''"));
+        let mut out = String::from(SCRIPT_HEADER);
+        for entry in self.find(|e| e.path != "/") {
+            if entry.is_dir {
+                out += &format!("d {}\n", quote(&entry.path));
+            }
+            else {
+                out += &format!("f {} {}\n", entry.size, quote(&entry.path));
+            }
+        }
+        out += "echo \"$root\"\n";
+        out
+    }
+}
+
+/// Like `tree -s`, but with directories' sizes including what's in them.
+impl fmt::Display for DirTree {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "[{:>11}]  /", self.sizes[DirTree::ROOT])?;
+        self.fmt_children(f, DirTree::ROOT, "")?;
+        let files: usize = self.nodes.iter().map(|d| d.files.len()).sum();
+        write!(f, "\n{} directories, {files} files", self.nodes.len() - 1)
+    }
+}
+
+/// Whether `name` matches a shell-style pattern, where `*` matches any run of
+/// characters and `?` any one.
+pub fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    // `matched[j]`: whether the pattern so far matches the first `j` characters.
+    let mut matched = vec![false; name.len() + 1];
+    matched[0] = true;
+    for p in pattern {
+        let prev = matched.clone();
+        matched[0] = p == '*' && prev[0];
+        for j in 1..=name.len() {
+            matched[j] = match p {
+                '*' => prev[j] || matched[j - 1],
+                '?' => prev[j - 1],
+                c => prev[j - 1] && name[j - 1] == c,
+            };
+        }
+    }
+    matched[name.len()]
+}
+
+/// A listed name has to name something in the directory itself, so that
+/// nothing built from the tree, like `to_script`'s paths, can point elsewhere.
+fn check_name(name: &str, column: usize, line: usize) -> Result<(), ParseError> {
+    if name.is_empty() || name == "." || name == ".." || name.contains(['/', '\0']) {
+        return Err(ParseError::new(column, name, "a file or directory name, without `/`").at_line(line));
+    }
+    Ok(())
+}
+
+pub struct Chdir {
+    dir: String,
+    column: usize,
+}
+
+pub struct InputFile {
+    name: String,
+    name_column: usize,
+    size: usize,
+}
+
+pub struct InputDir {
+    name: String,
+}
+
+pub enum Input {
+    Chdir(Chdir),
+    List,
+    File(InputFile),
+    Dir(InputDir),
+}
+
+impl FromStr for Input {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            static ref CDRE: Regex = Regex::new(r"^\$ cd (.*)").unwrap();
+        }
+        lazy_static! {
+            static ref DRE: Regex = Regex::new(r"^dir (.*)").unwrap();
+        }
+        lazy_static! {
+            static ref FRE: Regex = Regex::new(r"^(\d+) (.*)").unwrap();
+        }
+        if let Some(f) = try_match(&CDRE, s) {
+            Ok(Input::Chdir(Chdir {
+                dir: f.str(1).into(),
+                column: f.column(1),
+            }))
+        }
+        else if let Some(f) = try_match(&DRE, s) {
+            Ok(Input::Dir(InputDir {
+                name: f.str(1).into()
+            }))
+        }
+        else if let Some(f) = try_match(&FRE, s) {
+            Ok(Input::File(InputFile {
+                size: f.get(1, "a file size")?,
+                name: f.str(2).into(),
+                name_column: f.column(2),
+            }))
+        }
+        else if s == "$ ls" {
+            Ok(Input::List)
+        }
+        else {
+            Err(ParseError::new(1, s, "a `cd` or `ls` command or a directory listing"))
+        }
+    }
+}
+
+fn part1(tree: &DirTree) -> usize {
+    tree.dirs()
+        .map(|d| tree.size(d))
+        .filter(|size| *size <= 100000)
+        .sum()
+}
+
+fn part2(tree: &DirTree) -> usize {
+    let free = 70000000 - tree.size(DirTree::ROOT);
+    tree.dirs()
+        .map(|d| tree.size(d))
+        .filter(|size| free + size >= 30000000)
+        .min()
+        .unwrap()
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = DirTree;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    const DAY: u32 = 7;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        let input: Vec<Input> = lines(input)?;
+        DirTree::from_transcript(&input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+}
+
+/// Random input: a terminal session exploring `size` directories. File sizes
+/// are scaled so the disk is between 45 and 65 million full, as part 2 needs
+/// more than 40 million in use.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    struct Dir {
+        name: String,
+        children: Vec<usize>,
+        files: Vec<(String, u64)>,
+    }
+    fn name(rng: &mut Rng) -> String {
+        (0..1 + rng.below(8)).map(|_| (b'a' + rng.below(26) as u8) as char).collect()
+    }
+    fn visit(dirs: &[Dir], idx: usize, total: u64, scale: u64, out: &mut String) {
+        *out += "$ ls\n";
+        for child in &dirs[idx].children {
+            *out += &format!("dir {}\n", dirs[*child].name);
+        }
+        for (file, weight) in &dirs[idx].files {
+            *out += &format!("{} {file}\n", (weight * scale / total).max(1));
+        }
+        for child in &dirs[idx].children {
+            *out += &format!("$ cd {}\n", dirs[*child].name);
+            visit(dirs, *child, total, scale, out);
+            *out += "$ cd ..\n";
+        }
+    }
+
+    let mut dirs = vec![Dir { name: "/".into(), children: Vec::new(), files: Vec::new() }];
+    for idx in 1..=size {
+        let parent = rng.below(idx);
+        let mut dir_name = name(rng);
+        while dirs[parent].children.iter().any(|c| dirs[*c].name == dir_name) {
+            dir_name = name(rng);
+        }
+        dirs.push(Dir { name: dir_name, children: Vec::new(), files: Vec::new() });
+        dirs[parent].children.push(idx);
+    }
+    for dir in dirs.iter_mut() {
+        for n in 0..rng.below(5) {
+            let weight = rng.range(1..=1000).pow(3) as u64;
+            let ext = *rng.pick(&["", ".txt", ".dat", ".log"]);
+            dir.files.push((format!("{}{n}{ext}", name(rng)), weight));
+        }
+    }
+    if dirs[0].files.is_empty() {
+        dirs[0].files.push(("b.txt".into(), 1));
+    }
+    let total: u64 = dirs.iter().flat_map(|d| d.files.iter()).map(|f| f.1).sum();
+    let scale = rng.range(45_000_000..=65_000_000) as u64;
+
+    let mut out = String::from("$ cd /\n");
+    visit(&dirs, 0, total, scale, &mut out);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day07_test() {
+        let example = include_str!("day07.testinput");
+        let tree = Day07.parse(example).unwrap();
+        assert_eq!(part1(&tree), 95437);
+        assert_eq!(part2(&tree), 24933642);
+        let e = tree.get(tree.get(1).dirs["e"]);
+        assert_eq!((e.name.as_str(), tree.path(e.parent.unwrap())), ("e", "/a".into()));
+
+        // Going back to the root, past it, and listing it again changes nothing.
+        let revisited = example.to_string() + "$ cd /\n$ cd ..\n$ ls\ndir a\n14848514 b.txt\n$ cd a\n$ ls\n584 x\n";
+        let tree = Day07.parse(&revisited).unwrap();
+        assert_eq!((part1(&tree), tree.size(DirTree::ROOT)), (95437 + 584, 48381165 + 584));
+
+        let tree = Day07.parse(example).unwrap();
+        let du: Vec<(String, usize, usize)> = tree.du(Some(1)).into_iter()
+            .map(|(d, depth)| (tree.path(d), depth, tree.size(d)))
+            .collect();
+        assert_eq!(du, vec![("/a".into(), 1, 94853), ("/d".into(), 1, 24933642), ("/".into(), 0, 48381165)]);
+        assert_eq!(tree.du(None).iter().map(|(d, _)| tree.path(*d)).collect::<Vec<String>>(), vec!["/a/e", "/a", "/d", "/"]);
+        let found: Vec<String> = tree.find(|e| !e.is_dir && glob_match("*.*", e.path.rsplit('/').next().unwrap()))
+            .into_iter().map(|e| e.path).collect();
+        assert_eq!(found, vec!["/b.txt", "/c.dat", "/a/h.lst", "/d/d.ext", "/d/d.log"]);
+        let found: Vec<Entry> = tree.find(|e| e.is_dir && e.size < 1000);
+        assert_eq!(found, vec![Entry { path: "/a/e".into(), size: 584, is_dir: true }]);
+        assert_eq!(tree.largest(2), vec![DirTree::ROOT, tree.get(DirTree::ROOT).dirs["d"]]);
+        let (a, d) = (tree.get(DirTree::ROOT).dirs["a"], tree.get(DirTree::ROOT).dirs["d"]);
+        let e = tree.get(a).dirs["e"];
+        assert_eq!(tree.to_free(48381165 + 10, 5), Some(vec![]));
+        assert_eq!(tree.to_free(48381165 + 10, 20), Some(vec![e]));
+        // d alone is a byte short, and e with it beats a with it or the root.
+        assert_eq!(tree.to_free(48381165 + 10, 24933642 + 11), Some(vec![e, d]));
+        assert_eq!(tree.to_free(48381165 + 10, 48381165 + 10), Some(vec![DirTree::ROOT]));
+        assert_eq!(tree.to_free(48381165, 48381166), None);
+        assert_eq!(tree.to_free(48381164, 0), None);
+        assert_eq!(tree.to_string(), "\
+[   48381165]  /
+├── [      94853]  a
+│   ├── [        584]  e
+│   │   └── [        584]  i
+│   ├── [      29116]  f
+│   ├── [       2557]  g
+│   └── [      62596]  h.lst
+├── [   14848514]  b.txt
+├── [    8504156]  c.dat
+└── [   24933642]  d
+    ├── [    5626152]  d.ext
+    ├── [    8033020]  d.log
+    ├── [    4060174]  j
+    └── [    7214296]  k
+
+3 directories, 10 files");
+        let json = tree.to_json();
+        assert_eq!((json["size"].as_usize(), json["contents"].len()), (Some(48381165), 4));
+        assert_eq!(json["contents"][0]["contents"][0].dump(),
+            r#"{"type":"directory","name":"e","size":584,"contents":[{"type":"file","name":"i","size":584}]}"#);
+        let script = tree.to_script();
+        assert!(script.starts_with(SCRIPT_HEADER));
+        assert!(script.contains("\nd 'a/e'\nf 584 'a/e/i'\n"));
+        assert_eq!(script.lines().filter(|l| l.starts_with("d '")).count(), 3);
+        assert!(glob_match("a*b?c", "axxbyc") && glob_match("*", "") && !glob_match("a*b", "ab c"));
+
+        for (transcript, line, column, text) in [
+            ("$ cd /\n$ ls\n0 ../../../root/.bashrc\n", 3, 3, "../../../root/.bashrc"),
+            ("$ cd /\n$ ls\ndir ..\n", 3, 5, ".."),
+            ("$ cd /\n$ ls\n10 .\n", 3, 4, "."),
+            ("$ cd /\n$ ls\ndir \n", 3, 5, ""),
+            ("$ cd /\n$ ls\n10 a\n$ ls\n11 a\n", 5, 1, "11"),
+            ("$ cd /\n$ ls\n10 a\ndir a\n", 4, 5, "a"),
+            ("$ cd /\n$ ls\ndir a\n$ cd b\n", 4, 6, "b"),
+            ("$ cd /\n$ ls\ndir a\n$ cd a\n10 b\n", 5, 1, ""),
+        ] {
+            let err = Day07.parse(transcript).err().unwrap();
+            assert_eq!((err.line, err.column, err.text.as_str()), (line, column, text));
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use std::ops::Range;
-use ya_advent_lib::read::read_input;
 use ya_advent_lib::grid::Grid;
+use crate::{ParseError, Solution};
+use crate::parse::{check_chars, lines_with};
+use crate::rng::Rng;
 
 fn visible(grid: &Grid<i8>, tx: i64, ty: i64) -> bool {
     let h = grid.get(tx, ty);
@@ -76,12 +78,36 @@ fn part2(grid: &Grid<i8>) -> usize {
     maxv
 }
 
-fn main() {
-    let input = read_input::<String>();
-    let grid = Grid::from_input_map(&input, 0i8, 0, |c| (c as i8) - ('0' as i8));
+pub struct Day08;
 
-    println!("Part 1: {}", part1(&grid));
-    println!("Part 2: {}", part2(&grid));
+impl Solution for Day08 {
+    type Input = Grid<i8>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    const DAY: u32 = 8;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        let input: Vec<String> = lines_with(input, |l| Ok(l.to_string()))?;
+        check_chars(&input, 1, |c| c.to_digit(10).ok_or_else(|| ParseError::new(1, c, "a tree height 0-9")))?;
+        Ok(Grid::from_input_map(&input, 0i8, 0, |c| (c as i8) - ('0' as i8)))
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+}
+
+/// Random input: a forest `size` trees on a side.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let side = size.max(2);
+    (0..side)
+        .map(|_| (0..side).map(|_| (b'0' + rng.below(10) as u8) as char).chain(['\n']).collect::<String>())
+        .collect()
 }
 
 #[cfg(test)]
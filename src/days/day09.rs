@@ -2,36 +2,34 @@ use std::collections::HashSet;
 use std::str::FromStr;
 use lazy_static::lazy_static;
 use regex::Regex;
-use ya_advent_lib::read::read_input;
 use ya_advent_lib::coords::{CDir, Coord2D};
+use crate::{ParseError, Solution};
+use crate::parse::{lines, match_line};
+use crate::rng::Rng;
 
-struct Move {
+pub struct Move {
     dir: CDir,
     steps: i64,
 }
 
 impl FromStr for Move {
-    type Err = String;
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         lazy_static! {
-            static ref RE: Regex = Regex::new(r"(\w) (\d+)").unwrap();
-        }
-        if let Some(caps) = RE.captures(s) {
-            let dir = match caps.get(1).unwrap().as_str() {
-                "L" => CDir::W,
-                "R" => CDir::E,
-                "U" => CDir::N,
-                "D" => CDir::S,
-                _ => panic!(),
-            };
-            Ok(Move {
-                dir,
-                steps: caps.get(2).unwrap().as_str().parse::<i64>().unwrap(),
-            })
-        }
-        else {
-            Err("invalid input row".into())
+            static ref RE: Regex = Regex::new(r"^(\w) (\d+)$").unwrap();
         }
+        let f = match_line(&RE, s, "a direction and a step count like `R 4`")?;
+        let dir = match f.str(1) {
+            "L" => CDir::W,
+            "R" => CDir::E,
+            "U" => CDir::N,
+            "D" => CDir::S,
+            _ => { return Err(f.error(1, "a direction U, D, L or R")); },
+        };
+        Ok(Move {
+            dir,
+            steps: f.get(2, "a step count")?,
+        })
     }
 }
 
@@ -96,10 +94,33 @@ fn part2(input: &[Move]) -> usize {
     do_moves(input, 10)
 }
 
-fn main() {
-    let input = read_input::<Move>();
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = Vec<Move>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    const DAY: u32 = 9;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        lines(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+}
+
+/// Random input with `size` moves.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| format!("{} {}\n", rng.pick(&['U', 'D', 'L', 'R']), rng.range(1..=19)))
+        .collect()
 }
 
 #[cfg(test)]
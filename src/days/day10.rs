@@ -1,30 +1,27 @@
-#[macro_use] extern crate lazy_static;
 use std::vec::Vec;
 use std::str::FromStr;
+use lazy_static::lazy_static;
 use regex::Regex;
-use ya_advent_lib::read::read_input;
+use crate::{AnswerKind, ParseError, Solution};
+use crate::parse::{lines, match_line};
+use crate::rng::Rng;
 
-enum Instr {
+pub enum Instr {
     Addx(i32),
     Noop,
 }
 
 impl FromStr for Instr {
-    type Err = String;
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         lazy_static! {
-            static ref RE: Regex = Regex::new(r"^(\w+)(?: (-?\d+))?").unwrap();
+            static ref RE: Regex = Regex::new(r"^(\w+)(?: (\S+))?$").unwrap();
         }
-        if let Some(caps) = RE.captures(s) {
-            let op = caps.get(1).unwrap().as_str();
-            match op {
-                "noop" => Ok(Instr::Noop),
-                "addx" => Ok(Instr::Addx(caps.get(2).unwrap().as_str().parse::<i32>().unwrap())),
-                _ => Err("invalid opcode".into()),
-            }
-        }
-        else {
-            Err("invlid input line".into())
+        let f = match_line(&RE, s, "an instruction like `addx -3`")?;
+        match f.str(1) {
+            "noop" => Ok(Instr::Noop),
+            "addx" => Ok(Instr::Addx(f.get(2, "an integer operand")?)),
+            _ => Err(f.error(1, "`noop` or `addx`")),
         }
     }
 }
@@ -76,10 +73,50 @@ fn part2(input: &[Instr]) -> String {
     out
 }
 
-fn main() {
-    let input: Vec<Instr> = read_input();
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2:\n{}", part2(&input));
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<Instr>;
+    type Answer1 = i32;
+    type Answer2 = String;
+
+    const DAY: u32 = 10;
+    const KIND2: AnswerKind = AnswerKind::Art;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        lines(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+}
+
+/// Random input with `size` instructions, or more if that's needed to fill
+/// all 240 cycles. The sprite stays roughly on the screen.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+    let mut x = 1;
+    let mut cycles = 0;
+    let mut count = 0;
+    while count < size || cycles < 240 {
+        if rng.chance(1, 3) {
+            out += "noop\n";
+            cycles += 1;
+        }
+        else {
+            let next = rng.range(-1..=40);
+            out += &format!("addx {}\n", next - x);
+            x = next;
+            cycles += 2;
+        }
+        count += 1;
+    }
+    out
 }
 
 #[cfg(test)]
@@ -0,0 +1,328 @@
+use std::collections::VecDeque;
+use std::str::FromStr;
+use std::vec::Vec;
+use lazy_static::lazy_static;
+use regex::Regex;
+use crate::{ParseError, Solution};
+use crate::parse::{lines, parse_at, try_match};
+use crate::rng::Rng;
+
+pub enum Input {
+    Monkey,
+    StartItems(Vec<i64>),
+    OpAdd(i64),
+    OpMult(i64),
+    OpSquare,
+    Divisible(i64),
+    TrueTarget(usize),
+    FalseTarget(usize),
+    None,
+}
+
+impl FromStr for Input {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            static ref MRE: Regex = Regex::new(r"^Monkey (\d+)").unwrap();
+        }
+        lazy_static! {
+            static ref SRE: Regex = Regex::new(r"Starting items: ([\d, ]+)").unwrap();
+        }
+        lazy_static! {
+            static ref ORE: Regex = Regex::new(r"Operation: new = old (.) (old|\d+)").unwrap();
+        }
+        lazy_static! {
+            static ref DRE: Regex = Regex::new(r"divisible by (\d+)").unwrap();
+        }
+        lazy_static! {
+            static ref TFRE: Regex = Regex::new(r"(true|false):.* monkey (\d+)").unwrap();
+        }
+        if MRE.is_match(s) {
+            Ok(Input::Monkey)
+        }
+        else if let Some(f) = try_match(&SRE, s) {
+            let mut col = f.column(1);
+            let mut items: Vec<i64> = Vec::new();
+            for item in f.str(1).split(", ") {
+                items.push(parse_at(item, col, "a worry level")?);
+                col += item.len() + 2;
+            }
+            Ok(Input::StartItems(items))
+        }
+        else if let Some(f) = try_match(&ORE, s) {
+            match (f.str(1), f.str(2)) {
+                ("*", "old") => Ok(Input::OpSquare),
+                ("*", _) => Ok(Input::OpMult(f.get(2, "an integer or `old`")?)),
+                ("+", _) => Ok(Input::OpAdd(f.get(2, "an integer")?)),
+                (_, _) => Err(f.error(1, "`*` or `+`")),
+            }
+        }
+        else if let Some(f) = try_match(&DRE, s) {
+            match f.get(1, "a divisor")? {
+                0 => Err(f.error(1, "a nonzero divisor")),
+                n => Ok(Input::Divisible(n)),
+            }
+        }
+        else if let Some(f) = try_match(&TFRE, s) {
+            let val = f.get(2, "a monkey number")?;
+            match f.str(1) {
+                "true" => Ok(Input::TrueTarget(val)),
+                _ => Ok(Input::FalseTarget(val)),
+            }
+        }
+        else if s.trim().is_empty() {
+            Ok(Input::None)
+        }
+        else {
+            Err(ParseError::new(1, s, "a line of a monkey's description"))
+        }
+    }
+}
+
+enum Operation {
+    Add(i64),
+    Mult(i64),
+    Square,
+}
+
+struct Monkey {
+    items: VecDeque<i64>,
+    op: Operation,
+    divisible: i64,
+    true_target: usize,
+    false_target: usize,
+    inspections: i64,
+}
+
+fn make_monkeys(input: &[Input]) -> Vec<Monkey> {
+    let mut monkeys:Vec<Monkey> = Vec::new();
+    let mut op:Operation = Operation::Square;
+    let mut div:i64 = 0;
+    let mut true_target:usize = 0;
+    let mut items:VecDeque<i64> = VecDeque::new();
+
+    for row in input {
+        match row {
+            Input::OpSquare => {op = Operation::Square;},
+            Input::OpAdd(n) => {op = Operation::Add(*n);},
+            Input::OpMult(n) => {op = Operation::Mult(*n);},
+            Input::StartItems(v) => {items = VecDeque::from_iter(v.iter().cloned());},
+            Input::Divisible(n) => {div = *n;},
+            Input::TrueTarget(n) => {true_target = *n;},
+            Input::FalseTarget(n) => {
+                let false_target = *n;
+                monkeys.push(Monkey{
+                    items,
+                    op,
+                    divisible: div,
+                    true_target,
+                    false_target,
+                    inspections: 0,
+                });
+                items = VecDeque::new();
+                op = Operation::Square;
+            },
+            Input::Monkey => {},
+            Input::None => {},
+        }
+    }
+
+    monkeys
+}
+
+fn monkey_step(id: usize, modulo: i64, monkeys: &mut [Monkey]) -> bool {
+    if monkeys[id].items.is_empty() { return false; }
+    let mut item = monkeys.get_mut(id).unwrap().items.pop_front().unwrap();
+    monkeys.get_mut(id).unwrap().inspections += 1;
+    match monkeys[id].op {
+        Operation::Add(n) => { item += n; },
+        Operation::Mult(n) => { item *= n; },
+        Operation::Square => { item *= item; },
+    }
+    if modulo == 0 {
+        item /= 3;
+    } else {
+        item %= modulo;
+    }
+    let target = if item % monkeys[id].divisible == 0 {
+        monkeys[id].true_target
+    } else {
+        monkeys[id].false_target
+    };
+    monkeys.get_mut(target).unwrap().items.push_back(item);
+    !monkeys[id].items.is_empty()
+}
+
+fn bothparts(input: &[Input], part2: bool) -> i64 {
+    let mut monkeys = make_monkeys(input);
+    let (steps, modulo) = if part2 {
+        (10000, monkeys.iter().map(|m| m.divisible).product())
+    }
+    else {
+        (20, 0)
+    };
+    for _ in 0..steps {
+        for id in 0..monkeys.len() {
+            while monkey_step(id, modulo, &mut monkeys) {
+            }
+        }
+    }
+    let mut insp:Vec<i64> = monkeys.iter().map(|m| m.inspections).collect();
+    insp.sort_unstable_by(|a,b| b.cmp(a));
+    insp[0] * insp[1]
+}
+
+/// Checks that each monkey is described by its six lines in order, that there
+/// are at least two of them, and that each throws only to another monkey.
+fn check_monkeys(text: &str, input: &[Input]) -> Result<(), ParseError> {
+    const PARTS: [&str; 6] = [
+        "`Monkey N:`", "`Starting items:`", "`Operation:`", "`Test:`", "`If true:`", "`If false:`",
+    ];
+    let lines: Vec<&str> = text.lines().collect();
+    let mut next = 0;
+    let mut monkeys = 0;
+    for (idx, row) in input.iter().enumerate() {
+        let part = match row {
+            Input::None if next == 0 => continue,
+            Input::None => None,
+            Input::Monkey => Some(0),
+            Input::StartItems(_) => Some(1),
+            Input::OpAdd(_) | Input::OpMult(_) | Input::OpSquare => Some(2),
+            Input::Divisible(_) => Some(3),
+            Input::TrueTarget(_) => Some(4),
+            Input::FalseTarget(_) => Some(5),
+        };
+        if part != Some(next) {
+            return Err(ParseError::new(1, lines[idx], PARTS[next]).at_line(idx + 1));
+        }
+        if next == 5 {
+            monkeys += 1;
+        }
+        next = (next + 1) % PARTS.len();
+    }
+    if next != 0 {
+        return Err(ParseError::new(1, "", PARTS[next]).at_line(input.len() + 1));
+    }
+    if monkeys < 2 {
+        return Err(ParseError::new(1, "", "at least two monkeys").at_line(input.len() + 1));
+    }
+    let mut seen = 0;
+    for (idx, row) in input.iter().enumerate() {
+        let target = match row {
+            Input::Monkey => {
+                seen += 1;
+                continue;
+            },
+            Input::TrueTarget(t) | Input::FalseTarget(t) => *t,
+            _ => continue,
+        };
+        if target >= monkeys || target == seen - 1 {
+            let column = lines[idx].rfind(' ').unwrap() + 2;
+            let expected = format!("another monkey's number, below {monkeys}");
+            return Err(ParseError::new(column, target.to_string(), expected).at_line(idx + 1));
+        }
+    }
+    Ok(())
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Vec<Input>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    const DAY: u32 = 11;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        let rows: Vec<Input> = lines(input)?;
+        check_monkeys(input, &rows)?;
+        Ok(rows)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        bothparts(input, false)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        bothparts(input, true)
+    }
+}
+
+/// Random input with `size` monkeys, from 2 to 9 since each one's divisor is
+/// a different prime. As in the real puzzle one monkey squares. Inputs whose
+/// part 1 worry levels would overflow are rerolled.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    const PRIMES: [i64; 9] = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+    let n = size.clamp(2, 9);
+    loop {
+        let mut divisors = PRIMES;
+        rng.shuffle(&mut divisors);
+        let squarer = rng.below(n);
+        let mut out = String::new();
+        for (id, divisor) in divisors.iter().take(n).enumerate() {
+            let items: Vec<String> = (0..1 + rng.below(8)).map(|_| rng.range(50..=99).to_string()).collect();
+            let op = if id == squarer {
+                "* old".to_string()
+            }
+            else if rng.chance(1, 3) {
+                format!("* {}", rng.range(2..=19))
+            }
+            else {
+                format!("+ {}", rng.range(1..=8))
+            };
+            let if_true = (id + 1 + rng.below(n - 1)) % n;
+            let if_false = (id + 1 + rng.below(n - 1)) % n;
+            out += &format!("Monkey {id}:\n  Starting items: {}\n  Operation: new = old {op}\n  \
+                Test: divisible by {divisor}\n    If true: throw to monkey {if_true}\n    \
+                If false: throw to monkey {if_false}\n\n", items.join(", "));
+        }
+        out.pop();
+        let monkeys = make_monkeys(&lines::<Input>(&out).unwrap());
+        if part1_fits(monkeys) {
+            return out;
+        }
+    }
+}
+
+fn part1_fits(mut monkeys: Vec<Monkey>) -> bool {
+    for _ in 0..20 {
+        for id in 0..monkeys.len() {
+            while let Some(item) = monkeys[id].items.pop_front() {
+                let item = match monkeys[id].op {
+                    Operation::Add(n) => item.checked_add(n),
+                    Operation::Mult(n) => item.checked_mul(n),
+                    Operation::Square => item.checked_mul(item),
+                };
+                let Some(item) = item else { return false };
+                let monkey = &monkeys[id];
+                let target = if (item / 3) % monkey.divisible == 0 { monkey.true_target } else { monkey.false_target };
+                monkeys[target].items.push_back(item / 3);
+            }
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_advent_lib::read::test_input;
+
+    #[test]
+    fn day11_test() {
+        let input: Vec<Input> = test_input(include_str!("day11.testinput"));
+        assert_eq!(bothparts(&input, false), 10605);
+        assert_eq!(bothparts(&input, true), 2713310158);
+
+        let example = include_str!("day11.testinput");
+        assert!(Day11.parse(example).is_ok());
+        let truncated = example.split("    If false").next().unwrap();
+        let err = Day11.parse(truncated).err().unwrap();
+        assert_eq!((err.line, err.expected.as_str()), (6, "`If false:`"));
+        let err = Day11.parse(&example.replacen("throw to monkey 3", "throw to monkey 4", 1)).err().unwrap();
+        assert_eq!((err.line, err.column, err.text.as_str()), (6, 31, "4"));
+        let err = Day11.parse(&example.replacen("If true: throw to monkey 2", "If true: throw to monkey 0", 1)).err().unwrap();
+        assert_eq!((err.line, err.text.as_str()), (5, "0"));
+    }
+}
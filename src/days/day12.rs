@@ -0,0 +1,226 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use ya_advent_lib::grid::Grid;
+use crate::{ParseError, Solution};
+use crate::parse::{grid, lines_with};
+use crate::rng::Rng;
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum Cell {
+    Start,
+    End,
+    Elev(u8),
+    Uninitialized,
+}
+
+impl Cell {
+    fn elev(&self) -> u8 {
+        match self {
+            Cell::Elev(e) => *e,
+            Cell::Start => 0,
+            Cell::End => 25,
+            Cell::Uninitialized => 100,
+        }
+    }
+}
+impl TryFrom<char> for Cell {
+    type Error = ParseError;
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'S' => Ok(Cell::Start),
+            'E' => Ok(Cell::End),
+            'a'..='z' => Ok(Cell::Elev((value as u8) - b'a')),
+            _ => Err(ParseError::new(1, value, "an elevation a-z, S or E")),
+        }
+    }
+}
+
+#[derive(Clone, Eq, PartialEq)]
+struct State {
+    x: i64,
+    y: i64,
+    dist: i64,
+}
+
+impl State {
+    fn new(x: i64, y: i64, dist: i64) -> Self {
+        State {
+            dist,
+            x,
+            y,
+        }
+    }
+    fn key(&self) -> (i64, i64) {
+        (self.x, self.y)
+    }
+}
+
+// The priority queue depends on `Ord`.
+// Explicitly implement the trait so the queue becomes a min-heap
+// instead of a max-heap.
+impl Ord for State {
+    fn cmp(&self, other: &State) -> Ordering {
+        other.dist.cmp(&self.dist)
+            .then_with(|| other.x.cmp(&self.x))
+            .then_with(|| other.y.cmp(&self.y))
+    }
+}
+
+// `PartialOrd` needs to be implemented as well.
+impl PartialOrd for State {
+    fn partial_cmp(&self, other: &State) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn search(grid: &Grid<Cell>, startx: i64, starty: i64) -> i64 {
+    let mut dists: HashMap<(i64,i64), i64> = HashMap::new();
+    let mut heap: BinaryHeap<State> = BinaryHeap::new();
+    heap.push(State::new(startx, starty, 0));
+
+    while let Some(state) = heap.pop() {
+        if dists.contains_key(&state.key()) && state.dist > dists[&state.key()] {
+            continue;
+        }
+        let cell = grid.get(state.x, state.y);
+        if cell == Cell::End {
+            return state.dist;
+        }
+        let elev:u8 = cell.elev() ;
+        let mut check = |x: i64, y: i64| {
+            if !grid.x_bounds().contains(&x) || !grid.y_bounds().contains(&y) {
+                return;
+            }
+            let ncell = grid.get(x, y);
+            if ncell.elev() <= elev + 1 && (!dists.contains_key(&(x,y)) || state.dist + 1 < dists[&(x,y)]) {
+                dists.insert((x,y), state.dist + 1);
+                heap.push(State::new(x, y, state.dist + 1));
+            }
+        };
+        check(state.x + 1, state.y);
+        check(state.x, state.y + 1);
+        check(state.x - 1, state.y);
+        check(state.x, state.y - 1);
+    }
+    i64::MAX
+}
+
+fn part1(grid: &Grid<Cell>) -> i64 {
+    let (startx, starty) = grid.find(|c,_,_| c == Cell::Start).unwrap();
+    search(grid, startx, starty)
+}
+
+fn part2(grid: &Grid<Cell>) -> i64 {
+    let starts: Vec<(i64, i64)> = grid.iter_with_coord()
+        .filter(|(cell, _, _)| matches!(cell, Cell::Start | Cell::Elev(0)))
+        .map(|(_, x, y)| (x, y))
+        .collect();
+    #[cfg(feature = "parallel")]
+    let starts = starts.into_par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let starts = starts.into_iter();
+    starts
+        .map(|(x, y)| search(grid, x, y))
+        .min()
+        .unwrap()
+}
+
+/// Also checks that there's exactly one `S` and one `E`.
+fn mkgrid(input: Vec<String>) -> Result<Grid<Cell>, ParseError> {
+    for (mark, expected) in [('S', "the start, `S`"), ('E', "the best signal, `E`")] {
+        let found: Vec<(usize, usize)> = input.iter().enumerate()
+            .flat_map(|(y, row)| row.chars().enumerate().filter(|(_, c)| *c == mark).map(move |(x, _)| (y, x)))
+            .collect();
+        match found[..] {
+            [] => return Err(ParseError::new(1, "", expected).at_line(input.len() + 1)),
+            [_] => {},
+            [_, (y, x), ..] => {
+                let expected = format!("only one {mark}");
+                return Err(ParseError::new(x + 1, mark, expected).at_line(y + 1));
+            },
+        }
+    }
+    grid(&input, 1, Cell::Uninitialized, 0)
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Grid<Cell>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    const DAY: u32 = 12;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        mkgrid(lines_with(input, |l| Ok(l.to_string()))?)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+}
+
+/// Random input: a heightmap `size` squares wide (at least 26) and a quarter
+/// as tall. Heights rise from `a` on the left to `z` on the right with random
+/// dips, and a winding path without dips guarantees a route from S to E.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let width = size.max(26);
+    let height = (width / 4).max(5);
+    let base = |x: usize| x * 25 / (width - 1);
+    let mut rows: Vec<Vec<usize>> = (0..height)
+        .map(|_| (0..width).map(|x| base(x).saturating_sub(rng.below(5))).collect())
+        .collect();
+    let start = rng.below(height);
+    let mut path: Vec<(usize, usize)> = Vec::new();
+    let mut y = start;
+    for x in 0..width {
+        let to = rng.below(height);
+        while y != to {
+            path.push((x, y));
+            y = if to > y { y + 1 } else { y - 1 };
+        }
+        path.push((x, y));
+    }
+    for (x, path_y) in path {
+        rows[path_y][x] = base(x);
+    }
+    let mut out = String::new();
+    for (row_y, row) in rows.iter().enumerate() {
+        for (x, h) in row.iter().enumerate() {
+            out.push(match (x, row_y) {
+                (0, _) if row_y == start => 'S',
+                _ if x == width - 1 && row_y == y => 'E',
+                _ => (b'a' + *h as u8) as char,
+            });
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_advent_lib::read::test_input;
+
+    #[test]
+    fn day12_test() {
+        let input: Vec<String> = test_input(include_str!("day12.testinput"));
+        let grid = mkgrid(input).unwrap();
+        assert_eq!(part1(&grid), 31);
+        assert_eq!(part2(&grid), 29);
+
+        let example = include_str!("day12.testinput");
+        let err = Day12.parse(&example.replace('S', "a")).err().unwrap();
+        assert_eq!((err.line, err.expected.as_str()), (6, "the start, `S`"));
+        let err = Day12.parse(&example.replacen('a', "E", 1)).err().unwrap();
+        assert_eq!((err.line, err.column, err.text.as_str()), (3, 6, "E"));
+    }
+}
@@ -3,14 +3,33 @@ use std::str::FromStr;
 use std::vec::Vec;
 
 use json::JsonValue;
-use ya_advent_lib::read::read_grouped_input;
+use crate::{ParseError, Solution};
+use crate::parse::groups;
+use crate::rng::Rng;
 
-struct JsonValueWrapper(JsonValue);
+pub struct JsonValueWrapper(JsonValue);
+
+fn is_packet(value: &JsonValue) -> bool {
+    match value {
+        JsonValue::Number(_) => true,
+        JsonValue::Array(a) => a.iter().all(is_packet),
+        _ => false,
+    }
+}
 
 impl FromStr for JsonValueWrapper {
-    type Err = json::JsonError;
-    fn from_str(s: &str) -> Result<Self, json::JsonError> {
-        let value = json::parse(s)?;
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let value = json::parse(s).map_err(|e| match e {
+            json::Error::UnexpectedCharacter { ch, column, .. } =>
+                ParseError::new(column, ch, "a list of lists and integers"),
+            json::Error::UnexpectedEndOfJson =>
+                ParseError::new(s.chars().count() + 1, "", "the rest of the list"),
+            _ => ParseError::new(1, s, "a list of lists and integers"),
+        })?;
+        if !value.is_array() || !is_packet(&value) {
+            return Err(ParseError::new(1, s, "a list of lists and integers"));
+        }
         Ok(JsonValueWrapper(value))
     }
 }
@@ -67,10 +86,45 @@ fn part2(input: &[Vec<JsonValueWrapper>]) -> usize {
     idx1 * idx2
 }
 
-fn main() {
-    let input: Vec<Vec<JsonValueWrapper>> = read_grouped_input();
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<Vec<JsonValueWrapper>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    const DAY: u32 = 13;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        groups(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+}
+
+/// Random input with `size` pairs of packets. Pairs that compare equal are
+/// rerolled, since part 1 has no answer for them.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    fn packet(rng: &mut Rng, depth: usize) -> String {
+        let items: Vec<String> = (0..rng.below(6))
+            .map(|_| if depth < 4 && rng.chance(1, 3) { packet(rng, depth + 1) } else { rng.below(11).to_string() })
+            .collect();
+        format!("[{}]", items.join(","))
+    }
+    let mut pairs: Vec<String> = Vec::new();
+    while pairs.len() < size.max(1) {
+        let (left, right) = (packet(rng, 0), packet(rng, 0));
+        if check_order(&json::parse(&left).unwrap(), &json::parse(&right).unwrap()) != Ordering::Equal {
+            pairs.push(format!("{left}\n{right}\n"));
+        }
+    }
+    pairs.join("\n")
 }
 
 #[cfg(test)]
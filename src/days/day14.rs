@@ -1,21 +1,28 @@
 use std::str::FromStr;
 use std::vec::Vec;
-use ya_advent_lib::read::read_input;
 use ya_advent_lib::infinite_grid::InfiniteGrid;
+use crate::{ParseError, Solution};
+use crate::parse::{lines, parse_at};
+use crate::rng::Rng;
 
-struct CoordList {
+pub struct CoordList {
     list: Vec<(i64,i64)>
 }
 
 impl FromStr for CoordList {
-    type Err = String;
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let v: Vec<(i64, i64)> = s.split(" -> ")
-            .map(|c| {
-                let nn:Vec<i64> = c.split(',').map(|cc| cc.parse::<i64>().unwrap()).collect();
-                (nn[0], nn[1])
-            })
-            .collect();
+        let mut v: Vec<(i64, i64)> = Vec::new();
+        let mut col = 1;
+        for c in s.split(" -> ") {
+            let (x, y) = c.split_once(',')
+                .ok_or_else(|| ParseError::new(col, c, "a point like 498,4"))?;
+            v.push((
+                parse_at(x, col, "an x coordinate")?,
+                parse_at(y, col + x.len() + 1, "a y coordinate")?,
+            ));
+            col += c.len() + 4;
+        }
         Ok(CoordList {list: v})
     }
 }
@@ -113,10 +120,48 @@ fn part2(input: &[CoordList]) -> usize {
     count
 }
 
-fn main() {
-    let input: Vec<CoordList> = read_input::<CoordList>();
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Vec<CoordList>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    const DAY: u32 = 14;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        lines(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+}
+
+/// Random input with `size` rock paths below the sand source.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            let (mut x, mut y) = (rng.range(440..=560), rng.range(13..=170));
+            let mut points = vec![format!("{x},{y}")];
+            let horizontal = rng.chance(1, 2);
+            for seg in 0..1 + rng.below(5) {
+                let step = rng.range(1..=9) * if rng.chance(1, 2) { 1 } else { -1 };
+                if (seg % 2 == 0) == horizontal {
+                    x += step;
+                }
+                else {
+                    y = (y + step).max(1);
+                }
+                points.push(format!("{x},{y}"));
+            }
+            points.join(" -> ") + "\n"
+        })
+        .collect()
 }
 
 #[cfg(test)]
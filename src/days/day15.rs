@@ -0,0 +1,295 @@
+use std::collections::HashSet;
+use std::fmt::{self, Display};
+use std::ops::Range;
+use std::str::FromStr;
+use lazy_static::lazy_static;
+use regex::Regex;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use ya_advent_lib::range::merge_ranges;
+use crate::{ParseError, Solution};
+use crate::check::{compare, Mismatch};
+use crate::parse::{lines, match_line};
+use crate::rng::Rng;
+
+pub struct Sensor {
+    loc: (i64,i64),
+    beacon: (i64,i64),
+}
+impl Sensor {
+    fn dx_to_beacon(&self) -> i64 {
+        (self.loc.0 - self.beacon.0).abs() + (self.loc.1 - self.beacon.1).abs()
+    }
+}
+
+impl FromStr for Sensor {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            static ref RE: Regex = Regex::new(r"x=([-\d]+), y=([-\d]+).*x=([-\d]+), y=([-\d]+)").unwrap();
+        }
+        let f = match_line(&RE, s, "a sensor and beacon position")?;
+        Ok(Sensor {
+            loc: (
+               f.get(1, "an x coordinate")?,
+               f.get(2, "a y coordinate")?,
+            ),
+            beacon: (
+               f.get(3, "an x coordinate")?,
+               f.get(4, "a y coordinate")?,
+            ),
+        })
+    }
+}
+
+fn bsearch(list: &[i64], range: &Range<i64>) -> usize {
+    let (start_has_val, start_idx) = match list.binary_search(&range.start) {
+        Ok(val) => (true, val),
+        Err(val) => (false, val),
+    };
+    let end_idx = match list.binary_search(&range.end) {
+        Ok(val) => val,
+        Err(val) => val,
+    };
+    if start_idx == end_idx {
+        if start_has_val {
+            1usize
+        } else {
+            0usize
+        }
+    } else {
+        end_idx - start_idx
+    }
+}
+
+fn searchrow(input: &[Sensor], tgtrow: i64) -> (Vec<Range<i64>>, Vec<i64>) {
+    let mut ranges:Vec<Range<i64>> = Vec::new();
+    let mut beacons: HashSet<i64> = HashSet::new();
+    for row in input {
+        let dx = row.dx_to_beacon();
+        if row.beacon.1 == tgtrow {
+            beacons.insert(row.beacon.0);
+        }
+        if row.loc.1 - dx <= tgtrow && row.loc.1 + dx >= tgtrow {
+            let xdx = dx - (row.loc.1 - tgtrow).abs();
+            ranges.push(Range{ start: row.loc.0 - xdx, end: row.loc.0 + xdx + 1 });
+        }
+    }
+    ranges.sort_by_key(|r| r.start);
+    let merged_ranges: Vec<_> = merge_ranges(ranges).collect();
+    let mut sorted_beacons = Vec::from_iter(beacons.iter().cloned());
+    sorted_beacons.sort();
+    (merged_ranges, sorted_beacons)
+}
+
+fn part1(input: &[Sensor], at_y: i64) -> i64 {
+    let (ranges, beacons) = searchrow(input, at_y);
+    let mut sum = 0i64;
+    for range in ranges {
+        let nbeacons = bsearch(&beacons, &range) as i64;
+        sum += range.end - range.start - nbeacons;
+    }
+    sum
+}
+
+/// Part 2's answer, the distress beacon's tuning frequency. There's none if
+/// the sensors cover the whole search area.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Tuning(pub Option<i64>);
+
+impl Tuning {
+    fn at(pos: Option<(i64, i64)>) -> Self {
+        Tuning(pos.map(|(x, y)| x * 4000000 + y))
+    }
+}
+
+impl Display for Tuning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Some(n) => write!(f, "{n}"),
+            None => write!(f, "-"),
+        }
+    }
+}
+
+fn part2(input: &[Sensor], max: i64) -> Tuning {
+    // The first x in 0..=max that none of the row's ranges, clipped to the
+    // search area, take in.
+    let gap = |row: i64| {
+        let (ranges, _) = searchrow(input, row);
+        let mut x = 0;
+        for range in ranges.iter().filter(|r| r.end > 0 && r.start <= max) {
+            if range.start > x {
+                return Some((x, row));
+            }
+            x = x.max(range.end);
+        }
+        (x <= max).then_some((x, row))
+    };
+    #[cfg(feature = "parallel")]
+    let found = (0..=max).into_par_iter().find_map_first(gap);
+    #[cfg(not(feature = "parallel"))]
+    let found = (0..=max).find_map(gap);
+    Tuning::at(found)
+}
+
+/// The row checked in part 1 and the search bound for part 2 differ
+/// between the example and the real puzzle.
+pub struct Day15 {
+    pub row: i64,
+    pub max: i64,
+}
+
+impl Day15 {
+    pub const PUZZLE: Self = Self { row: 2000000, max: 4000000 };
+    pub const EXAMPLE: Self = Self { row: 10, max: 20 };
+}
+
+impl Solution for Day15 {
+    type Input = Vec<Sensor>;
+    type Answer1 = i64;
+    type Answer2 = Tuning;
+
+    const DAY: u32 = 15;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        lines(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        part1(input, self.row)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        part2(input, self.max)
+    }
+}
+
+/// Random input with `size` sensors (at least four) for the full-size puzzle.
+/// Four sensors at the corners of a square centred on a hidden spot cover the
+/// whole search area except that spot. The rest fall short of the spot.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let max = Day15::PUZZLE.max;
+    let hidden = (rng.range(0..=max), rng.range(0..=max));
+    let mut sensors: Vec<((i64, i64), (i64, i64))> = [(-1, -1), (-1, 1), (1, -1), (1, 1)].iter()
+        .map(|(sx, sy)| ((hidden.0 + sx * max, hidden.1 + sy * max), (hidden.0 + sx, hidden.1)))
+        .collect();
+    while sensors.len() < size {
+        let loc = (rng.range(0..=max), rng.range(0..=max));
+        sensors.extend(short_of(rng, loc, hidden, 1));
+    }
+    format_sensors(rng, sensors)
+}
+
+/// Random sensors around a search area small enough to scan, then more near
+/// each position left uncovered until only a hidden spot is. The spot is on
+/// an edge half the time, and nothing covers past the area on purpose.
+fn generate_within(rng: &mut Rng, size: usize, max: i64) -> String {
+    let coord = |rng: &mut Rng| match rng.below(4) {
+        0 => 0,
+        1 => max,
+        _ => rng.range(0..=max),
+    };
+    let hidden = (coord(rng), coord(rng));
+    let side = max as usize + 1;
+    let mut covered = vec![false; side * side];
+    let mut sensors: Vec<((i64, i64), (i64, i64))> = Vec::new();
+    let mut next = 0;
+    while next < covered.len() {
+        let cell = ((next % side) as i64, (next / side) as i64);
+        if covered[next] || cell == hidden {
+            next += 1;
+            continue;
+        }
+        let sensor = if sensors.len() < size {
+            let loc = (rng.range(0..=max), rng.range(0..=max));
+            short_of(rng, loc, hidden, 1)
+        }
+        else {
+            let loc = (cell.0 + rng.range(-5..=5), cell.1 + rng.range(-5..=5));
+            short_of(rng, loc, hidden, distance(loc, cell).max(1))
+        };
+        let Some((loc, beacon)) = sensor else { continue };
+        let reach = distance(loc, beacon);
+        for (idx, c) in covered.iter_mut().enumerate() {
+            *c |= distance(loc, ((idx % side) as i64, (idx / side) as i64)) <= reach;
+        }
+        sensors.push((loc, beacon));
+    }
+    format_sensors(rng, sensors)
+}
+
+fn distance(a: (i64, i64), b: (i64, i64)) -> i64 {
+    (a.0 - b.0).abs() + (a.1 - b.1).abs()
+}
+
+/// A sensor at `loc` whose beacon is at least `min_reach` away but closer
+/// than `hidden`, if there's room for one.
+fn short_of(rng: &mut Rng, loc: (i64, i64), hidden: (i64, i64), min_reach: i64) -> Option<((i64, i64), (i64, i64))> {
+    let dist = distance(loc, hidden);
+    if min_reach > dist - 1 {
+        return None;
+    }
+    let reach = rng.range(min_reach..=dist - 1);
+    let dx = rng.range(-reach..=reach);
+    let dy = (reach - dx.abs()) * if rng.chance(1, 2) { 1 } else { -1 };
+    Some((loc, (loc.0 + dx, loc.1 + dy)))
+}
+
+fn format_sensors(rng: &mut Rng, mut sensors: Vec<((i64, i64), (i64, i64))>) -> String {
+    rng.shuffle(&mut sensors);
+    sensors.iter()
+        .map(|(s, b)| format!("Sensor at x={}, y={}: closest beacon is at x={}, y={}\n", s.0, s.1, b.0, b.1))
+        .collect()
+}
+
+fn reference_part1(input: &[Sensor], at_y: i64) -> i64 {
+    let reach = input.iter().map(|s| s.dx_to_beacon()).max().unwrap();
+    let xs = input.iter().map(|s| s.loc.0);
+    let (min_x, max_x) = (xs.clone().min().unwrap() - reach, xs.max().unwrap() + reach);
+    (min_x..=max_x)
+        .filter(|x| input.iter().all(|s| s.beacon != (*x, at_y)))
+        .filter(|x| input.iter().any(|s| (s.loc.0 - x).abs() + (s.loc.1 - at_y).abs() <= s.dx_to_beacon()))
+        .count() as i64
+}
+
+fn reference_part2(input: &[Sensor], max: i64) -> Tuning {
+    Tuning::at((0..=max)
+        .flat_map(|y| (0..=max).map(move |x| (x, y)))
+        .find(|(x, y)| input.iter().all(|s| (s.loc.0 - x).abs() + (s.loc.1 - y).abs() > s.dx_to_beacon())))
+}
+
+/// Checks the range merging against testing every position, on a search
+/// area small enough to scan.
+pub fn check(rng: &mut Rng) -> Result<(), Mismatch> {
+    let day = Day15 { row: 0, max: rng.range(10..=60) };
+    let day = Day15 { row: rng.range(0..=day.max), ..day };
+    let sensors = 4 + rng.below(8);
+    let text = generate_within(rng, sensors, day.max);
+    let input = day.parse(&text).unwrap();
+    compare(&text, &format!("part 1 on row {}", day.row), day.part1(&input), reference_part1(&input, day.row))?;
+    compare(&text, &format!("part 2 up to {}", day.max), day.part2(&input), reference_part2(&input, day.max))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day15_test() {
+        let day = Day15::EXAMPLE;
+        let input = day.parse(include_str!("day15.testinput")).unwrap();
+        assert_eq!(day.part1(&input), 26);
+        assert_eq!(day.part2(&input), Tuning(Some(56000011)));
+
+        // A hidden spot on the x=0 edge, and rows whose ranges only touch.
+        let day = Day15 { row: 0, max: 4 };
+        let edge = day.parse("Sensor at x=4, y=0: closest beacon is at x=4, y=6\n\
+            Sensor at x=2, y=4: closest beacon is at x=2, y=6\n").unwrap();
+        assert_eq!(day.part2(&edge), Tuning(Some(3)));
+        let touching = day.parse("Sensor at x=0, y=0: closest beacon is at x=4, y=0\n\
+            Sensor at x=4, y=4: closest beacon is at x=4, y=1\n").unwrap();
+        assert_eq!(day.part2(&touching), Tuning(None));
+        assert_eq!(day.part2(&touching).to_string(), "-");
+    }
+}
@@ -0,0 +1,374 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, BTreeSet, HashMap, VecDeque};
+use std::str::FromStr;
+use std::vec::Vec;
+use itertools::{Either, Itertools};
+use lazy_static::lazy_static;
+use regex::Regex;
+use crate::{ParseError, Solution};
+use crate::check::{compare, Mismatch};
+use crate::parse::{lines, match_line};
+use crate::rng::Rng;
+
+pub type ValveId = [char;2];
+pub type Flow = i32;
+pub type Minute = i32;
+
+fn str_to_valveid(s: &str, column: usize) -> Result<ValveId, ParseError> {
+    match s.chars().collect::<Vec<char>>()[..] {
+        [a, b] => Ok([a, b]),
+        _ => Err(ParseError::new(column, s, "a two-letter valve name")),
+    }
+}
+
+#[derive(Clone)]
+pub struct Valve {
+    name: ValveId,
+    rate: Flow,
+    neighbors: Vec<ValveId>,
+    /// Where the name and each neighbor are on the line, for errors.
+    columns: Vec<usize>,
+}
+
+impl FromStr for Valve {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            static ref RE: Regex = Regex::new(r"Valve (\w+).*rate=(\d+).*valves? (.*)").unwrap();
+        }
+        let f = match_line(&RE, s, "a valve with its flow rate and tunnels")?;
+        let mut neighbors: Vec<ValveId> = Vec::new();
+        let mut columns = vec![f.column(1)];
+        let mut col = f.column(3);
+        for n in f.str(3).split(", ") {
+            neighbors.push(str_to_valveid(n, col)?);
+            columns.push(col);
+            col += n.len() + 2;
+        }
+        Ok(Valve {
+            name: str_to_valveid(f.str(1), f.column(1))?,
+            rate: f.get(2, "a flow rate")?,
+            neighbors,
+            columns,
+        })
+    }
+}
+
+
+#[derive(Clone, Eq, PartialEq)]
+struct State {
+    minutes_remaining: Minute,
+    location: ValveId,
+    opened: BTreeSet<ValveId>,
+    total_release: Flow,
+}
+
+impl State {
+    fn new(minutes_remaining: Minute) -> Self {
+        Self {
+            minutes_remaining,
+            location: ['A';2],
+            opened: BTreeSet::new(),
+            total_release: 0,
+        }
+    }
+
+    fn branch<'a>(&'a self, valves: &'a HashMap<ValveId, Valve>, dxmap: &'a HashMap<(ValveId,ValveId), Minute>) -> impl Iterator<Item = Self> + 'a {
+        valves.iter()
+            .filter(|(_,v)| v.rate > 0)
+            .filter(|(k,_)| !self.opened.contains(*k))
+            .map(|entry| (entry, getdx(self.location, *entry.0, dxmap)))
+            .filter(|(_, d)| *d < self.minutes_remaining)
+            .map(|((k,v), d)| {
+                let mut opened = self.opened.clone();
+                opened.insert(*k);
+                Self {
+                    minutes_remaining: self.minutes_remaining - d - 1,
+                    location: *k,
+                    opened,
+                    total_release: self.total_release + v.rate * (self.minutes_remaining - d - 1),
+                }
+            })
+    }
+
+}
+
+fn getdx(loc1: ValveId, loc2: ValveId, dxmap: &HashMap<(ValveId,ValveId), Minute>) -> Minute {
+    if dxmap.contains_key(&(loc1,loc2)) {
+        dxmap[&(loc1,loc2)]
+    } else {
+        dxmap[&(loc2,loc1)]
+    }
+}
+
+fn dx_between(loc1: &ValveId, loc2: &ValveId, valves: &HashMap<ValveId,Valve>) -> Minute {
+    let mut heap: BinaryHeap<(Reverse<Minute>,&ValveId)> = BinaryHeap::new();
+    let mut dists: HashMap<&ValveId,Minute> = HashMap::new();
+    heap.push((Reverse(0), loc1));
+    dists.insert(loc1, 0);
+    while let Some((Reverse(dx), loc)) = heap.pop() {
+        if loc == loc2 {
+            return dx;
+        }
+        for n in valves[loc].neighbors.iter() {
+            let d = dx + 1;
+            if !dists.contains_key(&n) || d < dists[&n] {
+                heap.push((Reverse(d), n));
+                dists.insert(n, d);
+            }
+        }
+    }
+    panic!("no route found");
+}
+
+fn search(valves: &HashMap<ValveId,Valve>, dxmap: &HashMap<(ValveId,ValveId), Minute>, minutes: Minute) -> HashMap<BTreeSet<ValveId>,Flow> {
+
+    let mut max_released: HashMap<BTreeSet<ValveId>,Flow> = HashMap::new();
+
+    let mut q = VecDeque::new();
+    q.push_back(State::new(minutes));
+    while let Some(state) = q.pop_front() {
+        max_released
+            .entry(state.opened.clone())
+            .and_modify(|val| *val = state.total_release.max(*val))
+            .or_insert(state.total_release);
+
+        for newstate in state.branch(valves, dxmap) {
+            q.push_back(newstate);
+        }
+    }
+    max_released
+}
+
+fn part1(input: &HashMap<ValveId,Valve>, dxmap: &HashMap<(ValveId,ValveId), Minute>) -> Flow {
+    let states = search(input, dxmap, 30);
+    states.values().copied().max().unwrap()
+}
+
+fn part2(input: &HashMap<ValveId,Valve>, dxmap: &HashMap<(ValveId,ValveId), Minute>) -> Flow {
+    let states = search(input, dxmap, 26);
+    states
+        .iter()
+        .tuple_combinations()
+        .filter(|(human, elephant)| human.0.is_disjoint(elephant.0))
+        .map(|(human, elephant)| human.1 + elephant.1)
+        .max()
+        // With nothing worth opening there's only the one, empty, state.
+        .unwrap_or_else(|| states.values().copied().max().unwrap())
+}
+
+pub type Input = (HashMap<ValveId, Valve>, HashMap<(ValveId,ValveId), Minute>);
+
+fn setup(input: Vec<Valve>) -> Input {
+    let valves: HashMap<ValveId, Valve> = input.iter().map(|r| (r.name, r.clone())).collect();
+    let dxmap: HashMap<(ValveId,ValveId), Minute> =
+        valves
+        .iter()
+        .filter(|(k,v)| v.rate > 0 || **k == ['A';2])
+        .map(|(k,_)| k)
+        .tuple_combinations()
+        .map(|(a, b)| ((*a,*b), dx_between(a, b, &valves)))
+        .collect();
+    (valves, dxmap)
+}
+
+/// Checks that `AA` and every valve a tunnel leads to are declared, once
+/// each, and that `AA` and the valves worth opening can all reach each other.
+fn check_valves(input: &[Valve]) -> Result<(), ParseError> {
+    let name = |id: &ValveId| id.iter().collect::<String>();
+    let mut lines: HashMap<ValveId, usize> = HashMap::new();
+    for (idx, valve) in input.iter().enumerate() {
+        if let Some(line) = lines.insert(valve.name, idx) {
+            let expected = format!("a valve not already declared on line {}", line + 1);
+            return Err(ParseError::new(valve.columns[0], name(&valve.name), expected).at_line(idx + 1));
+        }
+    }
+    if !lines.contains_key(&['A'; 2]) {
+        return Err(ParseError::new(1, "", "a line for valve AA").at_line(input.len() + 1));
+    }
+    for (idx, valve) in input.iter().enumerate() {
+        for (n, col) in valve.neighbors.iter().zip(&valve.columns[1..]) {
+            if !lines.contains_key(n) {
+                return Err(ParseError::new(*col, name(n), "a valve that has a line of its own").at_line(idx + 1));
+            }
+        }
+    }
+    let valves: HashMap<ValveId, &Valve> = input.iter().map(|v| (v.name, v)).collect();
+    let ends: Vec<ValveId> = input.iter().filter(|v| v.rate > 0 || v.name == ['A'; 2]).map(|v| v.name).collect();
+    for from in &ends {
+        let mut seen: BTreeSet<ValveId> = BTreeSet::from([*from]);
+        let mut queue: VecDeque<ValveId> = VecDeque::from([*from]);
+        while let Some(id) = queue.pop_front() {
+            queue.extend(valves[&id].neighbors.iter().filter(|n| seen.insert(**n)));
+        }
+        if let Some(to) = ends.iter().find(|id| !seen.contains(*id)) {
+            let idx = lines[to];
+            let expected = format!("a valve reachable from {}", name(from));
+            return Err(ParseError::new(input[idx].columns[0], name(to), expected).at_line(idx + 1));
+        }
+    }
+    Ok(())
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = Input;
+    type Answer1 = Flow;
+    type Answer2 = Flow;
+
+    const DAY: u32 = 16;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        let input: Vec<Valve> = lines(input)?;
+        check_valves(&input)?;
+        Ok(setup(input))
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        part1(&input.0, &input.1)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        part2(&input.0, &input.1)
+    }
+}
+
+/// Random input with `size` valves, joined by a random tree of tunnels plus a
+/// few loops. About a quarter of the valves, but no more than the real
+/// puzzle's 15, have a nonzero flow rate.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    generate_with(rng, size, (size / 4).clamp(1, 15))
+}
+
+fn generate_with(rng: &mut Rng, size: usize, flowing: usize) -> String {
+    let n = size.clamp(2, 26 * 26);
+    let mut names: Vec<String> = ('A'..='Z')
+        .flat_map(|a| ('A'..='Z').map(move |b| format!("{a}{b}")))
+        .filter(|name| name != "AA")
+        .collect();
+    rng.shuffle(&mut names);
+    names.insert(0, "AA".into());
+    names.truncate(n);
+
+    let mut tunnels: Vec<BTreeSet<usize>> = vec![BTreeSet::new(); n];
+    let mut connect = |a: usize, b: usize| {
+        if a != b {
+            tunnels[a].insert(b);
+            tunnels[b].insert(a);
+        }
+    };
+    for a in 1..n {
+        connect(a, rng.below(a));
+    }
+    for _ in 0..n / 5 {
+        connect(rng.below(n), rng.below(n));
+    }
+    let mut rates = vec![0; n];
+    let mut candidates: Vec<usize> = (1..n).collect();
+    rng.shuffle(&mut candidates);
+    for valve in candidates.into_iter().take(flowing) {
+        rates[valve] = rng.range(1..=25);
+    }
+
+    let mut order: Vec<usize> = (0..n).collect();
+    rng.shuffle(&mut order);
+    order.into_iter()
+        .map(|v| {
+            let mut to: Vec<&str> = tunnels[v].iter().map(|t| names[*t].as_str()).collect();
+            rng.shuffle(&mut to);
+            let tunnels = if to.len() == 1 { "tunnel leads to valve" } else { "tunnels lead to valves" };
+            format!("Valve {} has flow rate={}; {tunnels} {}\n", names[v], rates[v], to.join(", "))
+        })
+        .collect()
+}
+
+fn reference_distances(valves: &HashMap<ValveId, Valve>) -> HashMap<(ValveId, ValveId), Minute> {
+    let mut dist: HashMap<(ValveId, ValveId), Minute> = HashMap::new();
+    for start in valves.keys() {
+        let mut queue: VecDeque<(ValveId, Minute)> = VecDeque::from([(*start, 0)]);
+        while let Some((valve, d)) = queue.pop_front() {
+            if dist.contains_key(&(*start, valve)) {
+                continue;
+            }
+            dist.insert((*start, valve), d);
+            for n in &valves[&valve].neighbors {
+                queue.push_back((*n, d + 1));
+            }
+        }
+    }
+    dist
+}
+
+/// The most pressure one opener can release by opening some of `closed`,
+/// trying every order.
+fn reference_release(valves: &HashMap<ValveId, Valve>, dist: &HashMap<(ValveId, ValveId), Minute>,
+        at: ValveId, minutes: Minute, closed: &[ValveId]) -> Flow {
+    closed.iter().enumerate()
+        .filter_map(|(idx, valve)| {
+            let left = minutes - dist[&(at, *valve)] - 1;
+            (left > 0).then(|| {
+                let mut rest = closed.to_vec();
+                rest.remove(idx);
+                valves[valve].rate * left + reference_release(valves, dist, *valve, left, &rest)
+            })
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Checks the search against trying every order of opening the valves, and
+/// the pairing of disjoint sets in part 2 against trying every way of
+/// dividing the valves between the two openers.
+pub fn check(rng: &mut Rng) -> Result<(), Mismatch> {
+    let size = 5 + rng.below(11);
+    let flowing = 2 + rng.below(6.min(size - 2));
+    let text = generate_with(rng, size, flowing);
+    let (valves, dxmap) = Day16.parse(&text).unwrap();
+    let dist = reference_distances(&valves);
+    let closed: Vec<ValveId> = valves.values().filter(|v| v.rate > 0).map(|v| v.name).collect();
+    compare(&text, "part 1", part1(&valves, &dxmap), reference_release(&valves, &dist, ['A'; 2], 30, &closed))?;
+
+    let reference = (0..1usize << closed.len())
+        .map(|mask| {
+            let (mine, elephants): (Vec<ValveId>, Vec<ValveId>) = closed.iter()
+                .enumerate()
+                .partition_map(|(idx, v)| if mask & (1 << idx) != 0 { Either::Left(*v) } else { Either::Right(*v) });
+            reference_release(&valves, &dist, ['A'; 2], 26, &mine)
+                + reference_release(&valves, &dist, ['A'; 2], 26, &elephants)
+        })
+        .max()
+        .unwrap();
+    compare(&text, "part 2", part2(&valves, &dxmap), reference)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_advent_lib::read::test_input;
+
+    #[test]
+    fn day16_test() {
+        let input: Vec<Valve> = test_input(include_str!("day16.testinput"));
+        let (valves, dxmap) = setup(input);
+        assert_eq!(part1(&valves, &dxmap), 1651);
+        assert_eq!(part2(&valves, &dxmap), 1707);
+
+        let example = include_str!("day16.testinput");
+        for (text, line, column, bad) in [
+            (example.replace("Valve AA", "Valve AB"), 11, 1, ""),
+            (example.replacen("valves DD, II", "valves DD, ZZ", 1), 1, 54, "ZZ"),
+            (example.replacen("Valve CC", "Valve BB", 1), 3, 7, "BB"),
+        ] {
+            let err = Day16.parse(&text).err().unwrap();
+            assert_eq!((err.line, err.column, err.text.as_str()), (line, column, bad));
+        }
+        let err = Day16.parse("Valve AA has flow rate=0; tunnels lead to valves AA\n\
+            Valve BB has flow rate=3; tunnels lead to valves AA\n").err().unwrap();
+        assert_eq!((err.line, err.expected.as_str()), (2, "a valve reachable from AA"));
+
+        let input = Day16.parse("Valve AA has flow rate=0; tunnel leads to valve BB\n\
+            Valve BB has flow rate=0; tunnel leads to valve AA\n").unwrap();
+        assert_eq!((Day16.part1(&input), Day16.part2(&input)), (0, 0));
+    }
+}
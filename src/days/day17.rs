@@ -0,0 +1,249 @@
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::vec::Vec;
+use crate::{ParseError, Solution};
+use crate::check::{compare, Mismatch};
+use crate::parse::lines;
+use crate::rng::Rng;
+
+pub enum Push {
+    Left,
+    Right,
+}
+
+struct PushList(Vec<Push>);
+
+impl FromStr for PushList {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let pat = s.chars().enumerate().map(|(i, c)|
+            match c {
+                '<' => Ok(Push::Left),
+                '>' => Ok(Push::Right),
+                _ => Err(ParseError::new(i + 1, c, "`<` or `>`")),
+            }
+        ).collect::<Result<Vec<Push>, ParseError>>()?;
+        Ok(PushList(pat))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    Empty,
+    A,
+    B,
+    C,
+    D,
+    E,
+}
+
+struct Rock<'a> {
+    id: Cell,
+    cells: &'a [(usize,usize)],
+}
+
+const ROCKS: &[Rock] = &[
+    Rock { id: Cell::A, cells: &[ (0, 0), (1, 0), (2, 0), (3, 0) ]},
+    Rock { id: Cell::B, cells: &[ (1, 0), (0, 1), (1, 1), (2, 1), (1, 2) ]},
+    Rock { id: Cell::C, cells: &[ (0, 0), (1, 0), (2, 0), (2, 1), (2, 2) ]},
+    Rock { id: Cell::D, cells: &[ (0, 0), (0, 1), (0, 2), (0, 3) ]},
+    Rock { id: Cell::E, cells: &[ (0, 0), (1, 0), (0, 1), (1, 1) ]},
+];
+
+type Row = [Cell; 7];
+
+impl Rock<'_> {
+    fn collides(&self, x: usize, y: usize, shaft: &[Row]) -> bool {
+        for (cx, cy) in self.cells {
+            if cx + x > 6 { return true; }
+            if cy + y >= shaft.len() { continue; }
+            match shaft[cy + y][cx + x] {
+                Cell::Empty => { continue; },
+                _ => { return true; },
+            }
+        }
+        false
+    }
+
+    fn place(&self, x: usize, y: usize, shaft: &mut Vec<Row>) {
+        for (cx, cy) in self.cells {
+            while y + cy >= shaft.len() {
+                shaft.push([Cell::Empty; 7]);
+            }
+            shaft[y + cy][x + cx] = self.id;
+        }
+    }
+}
+
+/// Lets a rock fall until it comes to rest, pushed by the jets from `push_idx` on.
+fn drop_rock(rock: &Rock, pushpattern: &[Push], push_idx: &mut usize, shaft: &mut Vec<Row>) {
+    let mut y = shaft.len() + 3;
+    let mut x: usize = 2;
+    loop {
+        if let Some(newx) = match pushpattern[*push_idx] {
+            Push::Left => x.checked_sub(1),
+            Push::Right => x.checked_add(1),
+        } {
+            if !rock.collides(newx, y, shaft) {
+                x = newx;
+            }
+        }
+        *push_idx = (*push_idx + 1) % pushpattern.len();
+
+        if y == 0 || rock.collides(x, y - 1, shaft) {
+            rock.place(x, y, shaft);
+            return;
+        }
+        y -= 1;
+    }
+}
+
+/// How far below the top of the shaft `open_profile` looks. A column left open
+/// all the way down would otherwise make every profile different.
+const PROFILE_DEPTH: usize = 64;
+
+/// The empty cells a falling rock could still reach, as depths below the top
+/// of the shaft. Two moments with the same profile, next rock and next jet
+/// play out the same from then on. `None` if the reachable cells go on deeper
+/// than `PROFILE_DEPTH`, as then the profile doesn't pin that down.
+fn open_profile(shaft: &[Row]) -> Option<Vec<(usize, usize)>> {
+    let top = shaft.len().min(PROFILE_DEPTH);
+    let mut seen: HashSet<(usize, usize)> = HashSet::new();
+    let mut stack: Vec<(usize, usize)> = (0..7).map(|x| (x, 0)).collect();
+    while let Some((x, depth)) = stack.pop() {
+        if depth > top {
+            // Below the floor is fine; below the cutoff isn't.
+            if top < shaft.len() {
+                return None;
+            }
+            continue;
+        }
+        if (depth > 0 && shaft[shaft.len() - depth][x] != Cell::Empty) || !seen.insert((x, depth)) {
+            continue;
+        }
+        if x > 0 { stack.push((x - 1, depth)); }
+        if x < 6 { stack.push((x + 1, depth)); }
+        stack.push((x, depth + 1));
+    }
+    let mut profile: Vec<(usize, usize)> = seen.into_iter().collect();
+    profile.sort_unstable();
+    Some(profile)
+}
+
+/// The next rock, the next jet and the open profile.
+type State = (usize, usize, Vec<(usize, usize)>);
+
+fn simulate(pushpattern: &[Push], n_rocks: usize) -> usize {
+    let mut shaft: Vec<Row> = Vec::new();
+    let mut cur_rock_idx: usize = 0;
+    let mut cur_push_idx: usize = 0;
+    let mut seen: HashMap<State, (usize, usize)> = HashMap::new();
+    let mut rock_count: usize = 0;
+    let mut repeated_rows: usize = 0;
+
+    while rock_count < n_rocks {
+        drop_rock(&ROCKS[cur_rock_idx], pushpattern, &mut cur_push_idx, &mut shaft);
+
+        cur_rock_idx = (cur_rock_idx + 1) % ROCKS.len();
+        rock_count += 1;
+
+        if repeated_rows == 0 {
+            let Some(profile) = open_profile(&shaft) else { continue };
+            let key = (cur_rock_idx, cur_push_idx, profile);
+            if let Some((old_rock_count, old_top)) = seen.get(&key) {
+                let delta_top = shaft.len() - old_top;
+                let delta_rock_count = rock_count - old_rock_count;
+                let repeats = (n_rocks - rock_count) / delta_rock_count;
+                repeated_rows = repeats * delta_top;
+                rock_count += repeats * delta_rock_count;
+            }
+            else {
+                seen.insert(key, (rock_count, shaft.len()));
+            }
+        }
+    }
+    shaft.len() + repeated_rows
+}
+
+fn part1(pushpattern: &[Push]) -> usize {
+    simulate(pushpattern, 2022)
+}
+
+fn part2(pushpattern: &[Push]) -> usize {
+    simulate(pushpattern, 1000000000000)
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = Vec<Push>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    const DAY: u32 = 17;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        let input: Vec<PushList> = lines(input)?;
+        match input.into_iter().next() {
+            Some(PushList(pushes)) if !pushes.is_empty() => Ok(pushes),
+            _ => Err(ParseError::new(1, "", "a jet pattern").at_line(1)),
+        }
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+}
+
+/// Random input: a jet pattern `size` pushes long.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut out: String = (0..size.max(1)).map(|_| *rng.pick(&['<', '>'])).collect();
+    out.push('\n');
+    out
+}
+
+/// Drops every rock, without looking for a cycle to skip ahead with.
+fn reference_height(pushpattern: &[Push], n_rocks: usize) -> usize {
+    let mut shaft: Vec<Row> = Vec::new();
+    let mut push_idx = 0;
+    for rock in ROCKS.iter().cycle().take(n_rocks) {
+        drop_rock(rock, pushpattern, &mut push_idx, &mut shaft);
+    }
+    shaft.len()
+}
+
+/// Checks the cycle skipping against dropping every rock, for rock counts
+/// small enough to simulate in full.
+pub fn check(rng: &mut Rng) -> Result<(), Mismatch> {
+    let length = 1 + rng.below(40);
+    let text = generate(rng, length);
+    let pattern = Day17.parse(&text).unwrap();
+    for n_rocks in [2022, 2023 + rng.below(20000)] {
+        compare(&text, &format!("height after {n_rocks} rocks"),
+            simulate(&pattern, n_rocks), reference_height(&pattern, n_rocks))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_advent_lib::read::test_input;
+
+    #[test]
+    fn day17_test() {
+        let input: Vec<PushList> = test_input(include_str!("day17.testinput"));
+        assert_eq!(part1(&input[0].0), 3068);
+        assert_eq!(part2(&input[0].0), 1514285714288);
+
+        // A column left open all the way down: fine to the floor, not past the cutoff.
+        let mut row = [Cell::A; 7];
+        row[0] = Cell::Empty;
+        assert_eq!(open_profile(&[row; 10]).map(|p| p.len()), Some(7 + 10));
+        assert_eq!(open_profile(&[row; PROFILE_DEPTH + 1]), None);
+    }
+}
@@ -2,10 +2,12 @@ use std::cmp::{min,max};
 use std::collections::HashSet;
 use std::ops::Range;
 use std::vec::Vec;
-use ya_advent_lib::read::read_input;
 use ya_advent_lib::coords::Coord3D;
+use crate::{ParseError, Solution};
+use crate::parse::lines_with;
+use crate::rng::Rng;
 
-struct Area {
+pub struct Area {
     cubes: HashSet<Coord3D>,
     x_range: Range<i64>,
     y_range: Range<i64>,
@@ -75,11 +77,43 @@ fn part2(area: &Area) -> usize {
         .count()
 }
 
-fn main() {
-    let input: Vec<Coord3D> = read_input();
-    let area = Area::from_input(&input);
-    println!("Part 1: {}", part1(&area));
-    println!("Part 2: {}", part2(&area));
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Area;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    const DAY: u32 = 18;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        let input: Vec<Coord3D> = lines_with(input, |l|
+            l.parse().map_err(|_| ParseError::new(1, l, "a cube position like 2,2,2"))
+        )?;
+        Ok(Area::from_input(&input))
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+}
+
+/// Random input with `size` cubes, scattered through a box with room for
+/// about as much air as lava.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let side = ((size * 5 / 2) as f64).cbrt().ceil().max(2.0) as i64;
+    let mut cubes: Vec<(i64, i64, i64)> = (0..side)
+        .flat_map(|x| (0..side).flat_map(move |y| (0..side).map(move |z| (x, y, z))))
+        .collect();
+    rng.shuffle(&mut cubes);
+    cubes.iter()
+        .take(size.max(1))
+        .map(|(x, y, z)| format!("{x},{y},{z}\n"))
+        .collect()
 }
 
 #[cfg(test)]
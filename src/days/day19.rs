@@ -1,9 +1,15 @@
+use std::collections::HashMap;
 use std::ops::{Add, Mul};
 use std::str::FromStr;
 use std::vec::Vec;
 use lazy_static::lazy_static;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use regex::Regex;
-use ya_advent_lib::read::read_input;
+use crate::{ParseError, Solution};
+use crate::check::{compare, Mismatch};
+use crate::parse::{lines, match_line};
+use crate::rng::Rng;
 
 #[derive(Debug, Clone, Copy, Default)]
 struct Resources {
@@ -62,7 +68,7 @@ const ONE_OBSIDIAN: Resources = Resources {
     obsidian: 1,
 };
 
-struct Blueprint {
+pub struct Blueprint {
     ore_bot: Resources,
     clay_bot: Resources,
     obsidian_bot: Resources,
@@ -70,38 +76,34 @@ struct Blueprint {
 }
 
 impl FromStr for Blueprint {
-    type Err = String;
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         lazy_static! {
             static ref RE: Regex = Regex::new(r"s (\d+) ore.*s (\d+) ore.*s (\d+) ore and (\d+) c.*s (\d+) ore and (\d+) o").unwrap();
         }
-        if let Some(caps) = RE.captures(s) {
-            Ok(Blueprint {
-                ore_bot: Resources {
-                    ore: caps.get(1).unwrap().as_str().parse::<usize>().unwrap(),
-                    clay: 0,
-                    obsidian: 0,
-                },
-                clay_bot: Resources {
-                    ore: caps.get(2).unwrap().as_str().parse::<usize>().unwrap(),
-                    clay: 0,
-                    obsidian: 0,
-                },
-                obsidian_bot: Resources {
-                    ore: caps.get(3).unwrap().as_str().parse::<usize>().unwrap(),
-                    clay: caps.get(4).unwrap().as_str().parse::<usize>().unwrap(),
-                    obsidian: 0,
-                },
-                geode_bot: Resources {
-                    ore: caps.get(5).unwrap().as_str().parse::<usize>().unwrap(),
-                    obsidian: caps.get(6).unwrap().as_str().parse::<usize>().unwrap(),
-                    clay: 0,
-                },
-            })
-        }
-        else {
-            Err("invalid input line".into())
-        }
+        let f = match_line(&RE, s, "a blueprint with the costs of all four robots")?;
+        Ok(Blueprint {
+            ore_bot: Resources {
+                ore: f.get(1, "an ore cost")?,
+                clay: 0,
+                obsidian: 0,
+            },
+            clay_bot: Resources {
+                ore: f.get(2, "an ore cost")?,
+                clay: 0,
+                obsidian: 0,
+            },
+            obsidian_bot: Resources {
+                ore: f.get(3, "an ore cost")?,
+                clay: f.get(4, "a clay cost")?,
+                obsidian: 0,
+            },
+            geode_bot: Resources {
+                ore: f.get(5, "an ore cost")?,
+                obsidian: f.get(6, "an obsidian cost")?,
+                clay: 0,
+            },
+        })
     }
 }
 
@@ -205,7 +207,11 @@ fn search(bp: &Blueprint, timelimit: usize) -> usize {
 }
 
 fn part1(input: &[Blueprint]) -> usize {
-    input.iter().enumerate()
+    #[cfg(feature = "parallel")]
+    let blueprints = input.par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let blueprints = input.iter();
+    blueprints.enumerate()
         .map(|(idx, bp)| (idx + 1) * search(bp, 24))
         .sum()
 }
@@ -216,10 +222,75 @@ fn part2(input: &[Blueprint]) -> usize {
         .product()
 }
 
-fn main() {
-    let input: Vec<Blueprint> = read_input();
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = Vec<Blueprint>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    const DAY: u32 = 19;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        lines(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+}
+
+/// Random input with `size` blueprints, with costs in the real puzzle's ranges.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (1..=size.max(1))
+        .map(|id| format!(
+            "Blueprint {id}: Each ore robot costs {} ore. Each clay robot costs {} ore. \
+             Each obsidian robot costs {} ore and {} clay. Each geode robot costs {} ore and {} obsidian.\n",
+            rng.range(2..=4), rng.range(2..=4), rng.range(2..=4), rng.range(5..=20), rng.range(2..=4), rng.range(7..=20)))
+        .collect()
+}
+
+/// The most geodes that can be opened, trying every choice every minute.
+fn reference_geodes(bp: &Blueprint, minutes: usize) -> usize {
+    type Key = (usize, [usize; 6]);
+    fn best(bp: &Blueprint, minutes: usize, robots: Resources, stock: Resources, memo: &mut HashMap<Key, usize>) -> usize {
+        if minutes == 0 {
+            return 0;
+        }
+        let key = (minutes, [robots.ore, robots.clay, robots.obsidian, stock.ore, stock.clay, stock.obsidian]);
+        if let Some(geodes) = memo.get(&key) {
+            return *geodes;
+        }
+        let mut most = best(bp, minutes - 1, robots, stock + robots, memo);
+        for (cost, robot) in [(bp.ore_bot, ONE_ORE), (bp.clay_bot, ONE_CLAY), (bp.obsidian_bot, ONE_OBSIDIAN)] {
+            if let Some(left) = stock.checked_sub(cost) {
+                most = most.max(best(bp, minutes - 1, robots + robot, left + robots, memo));
+            }
+        }
+        if let Some(left) = stock.checked_sub(bp.geode_bot) {
+            most = most.max(minutes - 1 + best(bp, minutes - 1, robots, left + robots, memo));
+        }
+        memo.insert(key, most);
+        most
+    }
+    best(bp, minutes, ONE_ORE, Resources::default(), &mut HashMap::new())
+}
+
+/// Checks the branch and bound search against trying everything, with cheap
+/// robots and short time limits so that trying everything finishes.
+pub fn check(rng: &mut Rng) -> Result<(), Mismatch> {
+    let text = format!(
+        "Blueprint 1: Each ore robot costs {} ore. Each clay robot costs {} ore. \
+         Each obsidian robot costs {} ore and {} clay. Each geode robot costs {} ore and {} obsidian.\n",
+        rng.range(1..=3), rng.range(1..=3), rng.range(1..=3), rng.range(2..=8), rng.range(1..=3), rng.range(2..=8));
+    let blueprints = Day19.parse(&text).unwrap();
+    let minutes = 10 + rng.below(7);
+    compare(&text, &format!("geodes in {minutes} minutes"),
+        search(&blueprints[0], minutes), reference_geodes(&blueprints[0], minutes))
 }
 
 #[cfg(test)]
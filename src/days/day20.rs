@@ -0,0 +1,121 @@
+use std::vec::Vec;
+use itertools::Itertools;
+use crate::{ParseError, Solution};
+use crate::parse::{lines_with, parse_at};
+use crate::rng::Rng;
+
+struct Num {
+    value: i64,
+    initial_idx: usize,
+}
+
+fn movenum(array: &mut Vec<Num>, idx: usize, dx: i64) {
+    let mut newiidx:i64 = (idx as i64) + dx;
+    let len = array.len() as i64;
+    if newiidx < 0 || newiidx >= len - 1 {
+        newiidx = newiidx.rem_euclid(len - 1);
+    }
+    let newidx:usize = newiidx as usize;
+    let item = array.splice(idx..=idx, []).next().unwrap();
+    array.splice(newidx..newidx, [item]);
+}
+
+fn mix(input: &[i64], nums: &mut Vec<Num>) {
+    for (i, _) in input.iter().enumerate() {
+        let (idx, num) = nums.iter().enumerate().find(|(_, n)| n.initial_idx == i).unwrap();
+        if num.value != 0 {
+            movenum(nums, idx, num.value);
+        }
+    }
+    /*
+    println!("{}",
+        nums.iter().map(|n| n.value.to_string()).collect::<Vec<String>>().join(", ")
+    );
+    */
+}
+
+fn bothparts(input: &[i64], part2: bool) -> i64 {
+    let mut nums: Vec<Num> = Vec::with_capacity(input.len());
+    let key: i64 = if part2 { 811589153 } else { 1 };
+    for (idx, v) in input.iter().enumerate() {
+        nums.push(Num { value: *v * key, initial_idx: idx });
+    }
+    let n = if part2 { 10 } else { 1 };
+    for _ in 0..n {
+        mix(input, &mut nums);
+    }
+    let (idx, _) = nums.iter().enumerate().find(|(_, n)| n.value == 0).unwrap();
+    let n1 = nums[(idx + 1000) % nums.len()].value;
+    let n2 = nums[(idx + 2000) % nums.len()].value;
+    let n3 = nums[(idx + 3000) % nums.len()].value;
+    n1 + n2 + n3
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input = Vec<i64>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    const DAY: u32 = 20;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        let nums: Vec<i64> = lines_with(input, |l| parse_at(l, 1, "an integer"))?;
+        // Part 2 multiplies by the decryption key, and the answer is counted
+        // from the one 0.
+        for (idx, n) in nums.iter().enumerate() {
+            if n.checked_mul(811589153).is_none_or(|n| n.checked_add(nums.len() as i64).is_none()) {
+                return Err(ParseError::new(1, n.to_string(), "a number small enough to decrypt").at_line(idx + 1));
+            }
+        }
+        match nums.iter().positions(|n| *n == 0).collect::<Vec<usize>>()[..] {
+            [] => Err(ParseError::new(1, "", "a 0 somewhere in the list").at_line(nums.len() + 1)),
+            [_] => Ok(nums),
+            [_, second, ..] => Err(ParseError::new(1, "0", "only one 0").at_line(second + 1)),
+        }
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        bothparts(input, false)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        bothparts(input, true)
+    }
+}
+
+/// Random input with `size` numbers (at least two), exactly one of them 0.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let n = size.max(2);
+    let zero = rng.below(n);
+    (0..n)
+        .map(|i| {
+            let mut value = 0;
+            while value == 0 && i != zero {
+                value = rng.range(-10000..=10000);
+            }
+            format!("{value}\n")
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_advent_lib::read::test_input;
+
+    #[test]
+    fn day20_test() {
+        let input: Vec<i64> = test_input(include_str!("day20.testinput"));
+        assert_eq!(bothparts(&input, false), 3);
+        assert_eq!(bothparts(&input, true), 1623178306);
+
+        let err = Day20.parse("1\n2\n-3\n").err().unwrap();
+        assert_eq!((err.line, err.expected.as_str()), (4, "a 0 somewhere in the list"));
+        let err = Day20.parse("1\n0\n0\n").err().unwrap();
+        assert_eq!((err.line, err.text.as_str()), (3, "0"));
+        let err = Day20.parse("0\n99999999999\n").err().unwrap();
+        assert_eq!(err.line, 2);
+    }
+}
@@ -0,0 +1,421 @@
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::vec::Vec;
+use lazy_static::lazy_static;
+use regex::Regex;
+use crate::{ParseError, Solution};
+use crate::parse::{lines, try_match};
+use crate::rng::Rng;
+
+enum Op {
+    Const(i64),
+    Add(String,String),
+    Sub(String,String),
+    Mul(String,String),
+    Div(String,String),
+}
+
+pub struct Monkey {
+    name: String,
+    op: Op,
+    /// Where the operands are on the line, for errors.
+    columns: [usize; 2],
+}
+
+impl FromStr for Monkey {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            static ref CRE: Regex = Regex::new(r"^(\w+): (\d+)$").unwrap();
+        }
+        lazy_static! {
+            static ref RE: Regex = Regex::new(r"^(\w+): (\w+) (.) (\w+)$").unwrap();
+        }
+        if let Some(f) = try_match(&CRE, s) {
+            Ok(Monkey {
+                name: f.str(1).into(),
+                op: Op::Const(f.get(2, "a number")?),
+                columns: [0, 0],
+            })
+        } else if let Some(f) = try_match(&RE, s) {
+            let name:String = f.str(1).into();
+            let a:String = f.str(2).into();
+            let b:String = f.str(4).into();
+            Ok(Monkey {
+                name,
+                op: match f.str(3) {
+                    "+" => Op::Add(a, b),
+                    "-" => Op::Sub(a, b),
+                    "*" => Op::Mul(a, b),
+                    "/" => Op::Div(a, b),
+                    _ => { return Err(f.error(3, "an operator +, -, * or /")); },
+                },
+                columns: [f.column(2), f.column(4)],
+            })
+        }
+        else {
+            Err(ParseError::new(1, s, "a monkey name followed by a number or an operation"))
+        }
+    }
+}
+
+fn resolve(monkey: &str, monkeys: &HashMap<&str,&Monkey>) -> i64 {
+    match &monkeys[monkey].op {
+        Op::Const(n) => *n,
+        Op::Add(a, b) => resolve(a, monkeys) + resolve(b, monkeys),
+        Op::Sub(a, b) => resolve(a, monkeys) - resolve(b, monkeys),
+        Op::Mul(a, b) => resolve(a, monkeys) * resolve(b, monkeys),
+        Op::Div(a, b) => resolve(a, monkeys) / resolve(b, monkeys),
+    }
+}
+
+fn part1(input: &[Monkey]) -> i64 {
+    let monkeys: HashMap<&str,&Monkey> = HashMap::from_iter(
+        input.iter().map(|m| (m.name.as_str(), m)));
+    resolve("root", &monkeys)
+}
+
+enum HOp {
+    Human,
+    Add(i64,usize),
+    Mul(i64,usize),
+    Sub(i64,usize),
+    SubR(usize,i64),
+    Div(i64,usize),
+    DivR(usize,i64),
+}
+
+enum Res {
+    Const(i64),
+    Op(usize),
+}
+
+fn resolve_h(monkey: &str, monkeys: &HashMap<&str,&Monkey>, ops: &mut Vec<HOp>) -> Res {
+    if monkey == "humn" {
+        let idx = ops.len();
+        ops.push(HOp::Human);
+        return Res::Op(idx);
+    }
+    let res_a: Res;
+    let res_b: Res;
+    let op = &monkeys[monkey].op;
+    match op {
+        Op::Const(n) => { return Res::Const(*n); },
+        Op::Add(a, b) |
+        Op::Sub(a, b) |
+        Op::Mul(a, b) |
+        Op::Div(a, b) => {
+            res_a = resolve_h(a, monkeys, ops);
+            res_b = resolve_h(b, monkeys, ops);
+        },
+    };
+    match (res_a, res_b) {
+        (Res::Const(a), Res::Const(b)) => {
+            Res::Const(
+                match op {
+                    Op::Add(_,_) => a + b,
+                    Op::Sub(_,_) => a - b,
+                    Op::Mul(_,_) => a * b,
+                    Op::Div(_,_) => a / b,
+                    _ => panic!(),
+                }
+            )
+        },
+        (Res::Const(a_val), Res::Op(b_idx)) => {
+            let hop = match op {
+                Op::Add(_,_) => HOp::Add(a_val, b_idx),
+                Op::Sub(_,_) => HOp::Sub(a_val, b_idx),
+                Op::Mul(_,_) => HOp::Mul(a_val, b_idx),
+                Op::Div(_,_) => HOp::Div(a_val, b_idx),
+                _ => panic!(),
+            };
+            let idx = ops.len();
+            ops.push(hop);
+            Res::Op(idx)
+        },
+        (Res::Op(a_idx), Res::Const(b_val)) => {
+            let hop = match op {
+                Op::Add(_,_) => HOp::Add(b_val, a_idx),
+                Op::Sub(_,_) => HOp::SubR(a_idx, b_val),
+                Op::Mul(_,_) => HOp::Mul(b_val, a_idx),
+                Op::Div(_,_) => HOp::DivR(a_idx, b_val),
+                _ => panic!(),
+            };
+            let idx = ops.len();
+            ops.push(hop);
+            Res::Op(idx)
+        },
+        (Res::Op(_), Res::Op(_)) => panic!(),
+    }
+}
+
+#[allow(dead_code)]
+fn show(idx: usize, ops: &[HOp]) -> String {
+    match ops[idx] {
+        HOp::Human => "X".into(),
+        HOp::Add(val, i) => format!("({} + {})", val, show(i, ops)),
+        HOp::Sub(val, i) => format!("({} - {})", val, show(i, ops)),
+        HOp::SubR(i, val) => format!("({} - {})", show(i, ops), val),
+        HOp::Mul(val, i) => format!("({} * {})", val, show(i, ops)),
+        HOp::Div(val, i) => format!("({} / {})", val, show(i, ops)),
+        HOp::DivR(i, val) => format!("({} / {})", show(i, ops), val),
+    }
+}
+
+fn solve(initialvalue: i64, initialidx: usize, ops: &[HOp]) -> i64 {
+    let mut value = initialvalue;
+    let mut idx = initialidx;
+    loop {
+        match ops[idx] {
+            HOp::Human => { return value; },
+            HOp::Add(val, i) => { value -= val; idx = i; },
+            HOp::Mul(val, i) => { value /= val; idx = i; },
+            HOp::Sub(val, i) => { value = val - value; idx = i},
+            HOp::SubR(i, val) => { value += val; idx = i},
+            HOp::Div(val, i) => { value = val / value; idx = i},
+            HOp::DivR(i, val) => { value *= val; idx = i},
+        }
+    };
+}
+
+fn part2(input: &[Monkey]) -> i64 {
+    let monkeys: HashMap<&str,&Monkey> = HashMap::from_iter(
+        input.iter().map(|m| (m.name.as_str(), m)));
+    let root = monkeys["root"];
+    let (a, b) = match &root.op {
+        Op::Add(a, b) |
+        Op::Sub(a, b) |
+        Op::Mul(a, b) |
+        Op::Div(a, b) => (a, b),
+        _ => panic!(),
+    };
+    let mut ops: Vec<HOp> = Vec::new();
+    let av = resolve_h(a, &monkeys, &mut ops);
+    let bv = resolve_h(b, &monkeys, &mut ops);
+    let (val, opidx) = match (av, bv) {
+        (Res::Const(v), Res::Op(idx)) |
+        (Res::Op(idx), Res::Const(v)) => (v, idx),
+        _ => panic!(),
+    };
+    // println!("{} = {val}", show(opidx, &ops));
+    solve(val, opidx, &ops)
+}
+
+/// How many ways `name`'s number depends on `humn`'s, or an error if it
+/// depends on itself. `lines` is each monkey's line, 0-based.
+fn human_paths<'a>(name: &'a str, monkeys: &HashMap<&str, &'a Monkey>, lines: &HashMap<&str, usize>,
+        paths: &mut HashMap<&'a str, Option<usize>>) -> Result<usize, ParseError> {
+    match paths.get(name) {
+        Some(Some(n)) => return Ok(*n),
+        Some(None) => {
+            let expected = "a monkey whose number doesn't depend on itself";
+            return Err(ParseError::new(1, name, expected).at_line(lines[name] + 1));
+        },
+        None => {},
+    }
+    paths.insert(name, None);
+    let n = match &monkeys[name].op {
+        _ if name == "humn" => 1,
+        Op::Const(_) => 0,
+        Op::Add(a, b) | Op::Sub(a, b) | Op::Mul(a, b) | Op::Div(a, b) =>
+            human_paths(a, monkeys, lines, paths)? + human_paths(b, monkeys, lines, paths)?,
+    };
+    paths.insert(name, Some(n));
+    Ok(n)
+}
+
+/// Checks that every monkey is named once and only waits on monkeys that
+/// exist, that `root` waits on two others, and that `root`'s number depends
+/// on `humn`'s exactly once, so part 2 can work back from it.
+fn check_monkeys(input: &[Monkey]) -> Result<(), ParseError> {
+    let mut lines: HashMap<&str, usize> = HashMap::new();
+    for (idx, m) in input.iter().enumerate() {
+        if let Some(line) = lines.insert(&m.name, idx) {
+            let expected = format!("a monkey not already named on line {}", line + 1);
+            return Err(ParseError::new(1, m.name.as_str(), expected).at_line(idx + 1));
+        }
+    }
+    for (idx, m) in input.iter().enumerate() {
+        if let Op::Add(a, b) | Op::Sub(a, b) | Op::Mul(a, b) | Op::Div(a, b) = &m.op {
+            for (operand, column) in [a, b].into_iter().zip(m.columns) {
+                if !lines.contains_key(operand.as_str()) {
+                    return Err(ParseError::new(column, operand.as_str(), "a monkey with a line of its own").at_line(idx + 1));
+                }
+            }
+        }
+    }
+    for name in ["root", "humn"] {
+        if !lines.contains_key(name) {
+            return Err(ParseError::new(1, "", format!("a line for `{name}`")).at_line(input.len() + 1));
+        }
+    }
+    let root = lines["root"];
+    if matches!(input[root].op, Op::Const(_)) {
+        let expected = "an operation, as `root` compares two monkeys";
+        return Err(ParseError::new(7, input[root].name.as_str(), expected).at_line(root + 1));
+    }
+    let monkeys: HashMap<&str, &Monkey> = input.iter().map(|m| (m.name.as_str(), m)).collect();
+    let mut paths: HashMap<&str, Option<usize>> = HashMap::new();
+    if human_paths("root", &monkeys, &lines, &mut paths)? != 1 {
+        let expected = "a tree where only one path leads from `root` to `humn`";
+        return Err(ParseError::new(1, "humn", expected).at_line(lines["humn"] + 1));
+    }
+    Ok(())
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = Vec<Monkey>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    const DAY: u32 = 21;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        let input: Vec<Monkey> = lines(input)?;
+        check_monkeys(&input)?;
+        Ok(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+}
+
+enum GenNode {
+    Leaf(i64),
+    /// Holds what the human yells in part 2.
+    Human(i64),
+    Op(char, Box<GenNode>, Box<GenNode>),
+}
+
+impl GenNode {
+    /// Builds a tree with `budget` operations that yells `value`, with the
+    /// human somewhere in it if `human` is set.
+    fn build(rng: &mut Rng, value: i64, budget: usize, human: bool) -> Self {
+        if budget == 0 {
+            return if human { GenNode::Human(value) } else { GenNode::Leaf(value) };
+        }
+        let divisors: Vec<i64> = (2..=20).filter(|d| value > 0 && value % d == 0).collect();
+        let (op, a, b) = match rng.below(4) {
+            1 => ('-', value + rng.range(0..=1000), 0),
+            2 if !divisors.is_empty() => ('*', 0, *rng.pick(&divisors)),
+            3 if value <= 1_000_000 => ('/', 0, rng.range(1..=20)),
+            _ => ('+', rng.range(0..=value), 0),
+        };
+        let (a, b) = match op {
+            '-' => (a, a - value),
+            '*' => (value / b, b),
+            '/' => (value * b, b),
+            _ => (a, value - a),
+        };
+        let left_budget = rng.below(budget);
+        let right_budget = budget - 1 - left_budget;
+        // The human has to be dividend rather than divisor, and multiplied by
+        // the small nonzero factor rather than being it.
+        let human_left = human && (matches!(op, '*' | '/') || rng.chance(1, 2));
+        let human_right = human && !human_left;
+        GenNode::Op(op,
+            Box::new(GenNode::build(rng, a, left_budget, human_left)),
+            Box::new(GenNode::build(rng, b, right_budget, human_right)))
+    }
+
+    fn part2_answer(&self) -> Option<i64> {
+        match self {
+            GenNode::Leaf(_) => None,
+            GenNode::Human(n) => Some(*n),
+            GenNode::Op(_, a, b) => a.part2_answer().or_else(|| b.part2_answer()),
+        }
+    }
+
+    fn eval(&self, humn: i64) -> Option<i64> {
+        match self {
+            GenNode::Leaf(n) => Some(*n),
+            GenNode::Human(_) => Some(humn),
+            GenNode::Op(op, a, b) => {
+                let (a, b) = (a.eval(humn)?, b.eval(humn)?);
+                match op {
+                    '+' => a.checked_add(b),
+                    '-' => a.checked_sub(b),
+                    '*' => a.checked_mul(b),
+                    _ => a.checked_div(b),
+                }
+            },
+        }
+    }
+
+    fn write(&self, name: &str, names: &mut dyn FnMut() -> String, out: &mut Vec<String>) {
+        match self {
+            GenNode::Leaf(n) => out.push(format!("{name}: {n}")),
+            GenNode::Human(_) => {},
+            GenNode::Op(op, a, b) => {
+                let a_name = if matches!(**a, GenNode::Human(_)) { "humn".into() } else { names() };
+                let b_name = if matches!(**b, GenNode::Human(_)) { "humn".into() } else { names() };
+                out.push(format!("{name}: {a_name} {op} {b_name}"));
+                a.write(&a_name, names, out);
+                b.write(&b_name, names, out);
+            },
+        }
+    }
+}
+
+/// Random input with about `size` monkeys. The tree is built top down from
+/// what each monkey yells when the human yells the part 2 answer, so every
+/// division along the way is exact.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let target = rng.range(1000..=1_000_000);
+    let budget = size / 2;
+    let human_left = rng.chance(1, 2);
+    let left_budget = rng.below(budget.max(1));
+    let left = GenNode::build(rng, target, left_budget, human_left);
+    let right = GenNode::build(rng, target, budget.saturating_sub(left_budget + 1), !human_left);
+    let root = GenNode::Op('+', Box::new(left), Box::new(right));
+
+    // What the human yells in part 1 doesn't matter, as long as part 1
+    // doesn't overflow with it.
+    let mut humn = rng.range(1..=5000);
+    if root.eval(humn).is_none() {
+        humn = root.part2_answer().unwrap();
+    }
+
+    let mut used: HashSet<String> = HashSet::from(["root".into(), "humn".into()]);
+    let mut names = || loop {
+        let name: String = (0..4).map(|_| (b'a' + rng.below(26) as u8) as char).collect();
+        if used.insert(name.clone()) {
+            return name;
+        }
+    };
+    let mut lines = vec![format!("humn: {humn}")];
+    root.write("root", &mut names, &mut lines);
+    rng.shuffle(&mut lines);
+    lines.into_iter().map(|l| l + "\n").collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_advent_lib::read::test_input;
+
+    #[test]
+    fn day21_test() {
+        let input: Vec<Monkey> = test_input(include_str!("day21.testinput"));
+        assert_eq!(part1(&input), 152);
+        assert_eq!(part2(&input), 301);
+
+        let example = include_str!("day21.testinput");
+        for (text, line, column, bad) in [
+            (example.replace("root:", "toor:"), 16, 1, ""),
+            (example.replace("cczh: sllz + lgvd", "cczh: sllz + xxxx"), 3, 14, "xxxx"),
+            (example.replace("drzm: hmdt - zczc", "drzm: hmdt - root"), 1, 1, "root"),
+            (example.replace("ptdq: humn - dvpt", "ptdq: humn - humn"), 8, 1, "humn"),
+        ] {
+            let err = Day21.parse(&text).err().unwrap();
+            assert_eq!((err.line, err.column, err.text.as_str()), (line, column, bad));
+        }
+    }
+}
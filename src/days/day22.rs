@@ -3,11 +3,13 @@
 use std::vec::Vec;
 use regex::Regex;
 use ya_advent_lib::grid::Grid;
-use ya_advent_lib::read::read_grouped_input;
 use ya_advent_lib::coords::{CDir, Turn, Coord2D};
+use crate::{ParseError, Solution};
+use crate::parse::{grid, groups_with, parse_at};
+use crate::rng::Rng;
 
 #[derive(Debug, Copy, Clone)]
-enum Cell {
+pub enum Cell {
     Void,
     Open,
     Wall,
@@ -15,19 +17,20 @@ enum Cell {
     WrapCorner((Coord2D, CDir), (Coord2D, CDir)),
 }
 
-impl From<char> for Cell {
-    fn from(c: char) -> Self {
+impl TryFrom<char> for Cell {
+    type Error = ParseError;
+    fn try_from(c: char) -> Result<Self, Self::Error> {
         match c {
-            ' ' => Cell::Void,
-            '#' => Cell::Wall,
-            '.' => Cell::Open,
-            _ => panic!(),
+            ' ' => Ok(Cell::Void),
+            '#' => Ok(Cell::Wall),
+            '.' => Ok(Cell::Open),
+            _ => Err(ParseError::new(1, c, "a map tile `.`, `#` or space")),
         }
     }
 }
 
 #[derive(Debug, Copy, Clone)]
-enum Move {
+pub enum Move {
     Forward(usize),
     TurnLeft,
     TurnRight,
@@ -235,25 +238,75 @@ fn part(grid: &mut Grid<Cell>, moves: &[Move], partnum:i64) -> i64 {
     pos.password()
 }
 
-fn setup(input: Vec<Vec<String>>) -> (Grid<Cell>, Vec<Move>) {
-    let grid: Grid<Cell> = Grid::from_input(&input[0], Cell::Void, 1);
+fn setup(input: Vec<Vec<String>>) -> Result<(Grid<Cell>, Vec<Move>), ParseError> {
+    let path_line = input[0].len() + 2;
+    if input.len() < 2 {
+        return Err(ParseError::new(1, "", "a blank line and then the path").at_line(path_line));
+    }
+    let grid: Grid<Cell> = grid(&input[0], 1, Cell::Void, 1)?;
     let moves: Vec<Move> =
-        Regex::new(r"(\d+)|([LR])").unwrap()
+        Regex::new(r"\d+|.").unwrap()
         .find_iter(&input[1][0])
         .map(|m| match m.as_str() {
-            "L" => Move::TurnLeft,
-            "R" => Move::TurnRight,
-            d => Move::Forward(d.parse::<usize>().unwrap()),
+            "L" => Ok(Move::TurnLeft),
+            "R" => Ok(Move::TurnRight),
+            d => parse_at(d, m.start() + 1, "a step count, L or R").map(Move::Forward),
         })
-        .collect();
-    (grid, moves)
+        .collect::<Result<Vec<Move>, ParseError>>()
+        .map_err(|e| e.at_line(path_line))?;
+    Ok((grid, moves))
 }
 
-fn main() {
-    let input: Vec<Vec<String>> = read_grouped_input::<String>();
-    let (mut grid, moves) = setup(input);
-    println!("Part 1: {}", part(&mut grid, &moves, 1));
-    println!("Part 2: {}", part(&mut grid, &moves, 2));
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input = (Grid<Cell>, Vec<Move>);
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    const DAY: u32 = 22;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        let input: Vec<Vec<String>> = groups_with(input, |l| Ok(l.to_string()))?;
+        if input.is_empty() {
+            return Err(ParseError::new(1, "", "a map").at_line(1));
+        }
+        setup(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        part(&mut input.0.clone(), &input.1, 1)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        part(&mut input.0.clone(), &input.1, 2)
+    }
+}
+
+/// Random input: a map laid out in the real puzzle's cube net of 50-square
+/// faces, which part 2 is written for, and a path of `size` moves.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    const FACES: [(usize, usize); 6] = [(1, 0), (2, 0), (1, 1), (0, 2), (1, 2), (0, 3)];
+    let mut out = String::new();
+    for y in 0..200 {
+        for x in 0..150 {
+            if !FACES.contains(&(x / 50, y / 50)) {
+                if x < 50 && y < 100 { out.push(' '); }
+                continue;
+            }
+            out.push(if (x, y) != (50, 0) && rng.chance(1, 8) { '#' } else { '.' });
+        }
+        out.push('\n');
+    }
+    out.push('\n');
+    for n in 0..size.max(1) {
+        if n > 0 {
+            out.push(*rng.pick(&['L', 'R']));
+        }
+        out += &rng.range(1..=50).to_string();
+    }
+    out.push('\n');
+    out
 }
 
 #[cfg(test)]
@@ -264,7 +317,7 @@ mod tests {
     #[test]
     fn day22_test() {
         let input: Vec<Vec<String>> = grouped_test_input(include_str!("day22.testinput"));
-        let (mut grid, moves) = setup(input);
+        let (mut grid, moves) = setup(input).unwrap();
         assert_eq!(part(&mut grid, &moves, 1), 6032);
         // Cube fold currently only works on my real input
         // assert_eq!(part(&mut grid, &moves, 2), 5031);
@@ -3,8 +3,10 @@ use std::cmp::{min, max};
 use std::ops::Range;
 use std::vec::Vec;
 use ya_advent_lib::infinite_grid::InfiniteGrid;
-use ya_advent_lib::read::read_input;
 use ya_advent_lib::coords::{CDir, Coord2D};
+use crate::{ParseError, Solution};
+use crate::parse::{check_chars, lines_with};
+use crate::rng::Rng;
 
 #[derive(Copy, Clone)]
 enum Cell {
@@ -132,10 +134,39 @@ fn part2(input: &[String]) -> usize {
     value
 }
 
-fn main() {
-    let input: Vec<String> = read_input();
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input = Vec<String>;
+    type Answer1 = i64;
+    type Answer2 = usize;
+
+    const DAY: u32 = 23;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        let input: Vec<String> = lines_with(input, |l| Ok(l.to_string()))?;
+        check_chars(&input, 1, |c| match c {
+            '.' | '#' => Ok(()),
+            _ => Err(ParseError::new(1, c, "`.` or `#`")),
+        })?;
+        Ok(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+}
+
+/// Random input: a square `size` tiles on a side, about half of them elves.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let side = size.max(1);
+    (0..side)
+        .map(|_| (0..side).map(|_| if rng.chance(1, 2) { '#' } else { '.' }).chain(['\n']).collect::<String>())
+        .collect()
 }
 
 #[cfg(test)]
@@ -1,8 +1,10 @@
-use std::collections::{BinaryHeap, HashSet, HashMap};
+use std::collections::{BinaryHeap, HashSet, HashMap, VecDeque};
 use std::cmp::Ordering;
 use std::vec::Vec;
 use ya_advent_lib::coords::{CDir, Coord2D};
-use ya_advent_lib::read::read_input;
+use crate::{ParseError, Solution};
+use crate::parse::lines_with;
+use crate::rng::Rng;
 
 type Step = i64;
 type Leg = i64;
@@ -46,18 +48,18 @@ impl PartialOrd for State {
     }
 }
 
-struct Valley {
+pub struct Valley {
     width: i64,
     height: i64,
     blizzards: Vec<Blizzard>,
 }
 
 impl Valley {
-    fn from_input(input: Vec<String>) -> Self {
+    fn from_input(input: Vec<String>) -> Result<Self, ParseError> {
         let mut height = 0i64;
         let width:i64 = input[0].len() as i64 - 2;
         let mut blizzards: Vec<Blizzard> = Vec::new();
-        for line in input.iter().skip(1) {
+        for (n, line) in input.iter().enumerate().skip(1) {
             if line.contains("######") {
                 continue;
             }
@@ -71,7 +73,10 @@ impl Valley {
                     '>' => CDir::E,
                     'v' => CDir::S,
                     '<' => CDir::W,
-                    _ => panic!(),
+                    _ => {
+                        let e = ParseError::new(x + 2, c, "`.`, `#` or a blizzard ^, >, v or <");
+                        return Err(e.at_line(n + 1));
+                    },
                 };
                 blizzards.push(Blizzard {
                     initial_pos: Coord2D::new(x as i64, height),
@@ -80,11 +85,11 @@ impl Valley {
             }
             height += 1;
         }
-        Self {
+        Ok(Self {
             blizzards,
             height,
             width,
-        }
+        })
     }
 
     fn search(&self, nlegs: Leg) -> i64 {
@@ -113,7 +118,7 @@ impl Valley {
 
         let mut leg: Leg = 0;
         let mut heap: BinaryHeap<State> = BinaryHeap::new();
-        heap.push(State { pos: entrance, dist: &entrance.mdist_to(&exit) * nlegs, step: 0, leg: 0 });
+        heap.push(State { pos: entrance, dist: entrance.mdist_to(&exit) * nlegs, step: 0, leg: 0 });
         while let Some(state) = heap.pop() {
             //println!("state {:?}", state);
             if state.leg < leg { continue; }
@@ -214,14 +219,107 @@ impl Valley {
     }
 }
 
-fn main() {
-    let input: Vec<String> = read_input();
-    let valley = Valley::from_input(input);
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Input = Valley;
+    type Answer1 = i64;
+    type Answer2 = i64;
 
-    let val = valley.search(1);
-    println!("Part 1: {val}");
-    let val = valley.search(3);
-    println!("Part 2: {val}");
+    const DAY: u32 = 24;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        let input: Vec<String> = lines_with(input, |l| Ok(l.to_string()))?;
+        if input.is_empty() {
+            return Err(ParseError::new(1, "", "a wall like #.####").at_line(1));
+        }
+        Valley::from_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        input.search(1)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        input.search(3)
+    }
+}
+
+/// Whether the trip there, back and there again is possible, found by a
+/// breadth-first search over positions and times within the blizzards' cycle.
+fn crossable(rows: &[Vec<char>]) -> bool {
+    let (width, height) = (rows[0].len() as i64, rows.len() as i64);
+    let gcd = |mut a: i64, mut b: i64| { while b != 0 { (a, b) = (b, a % b); } a };
+    let period = width * height / gcd(width, height);
+    let mut blocked: HashSet<(i64, i64, i64)> = HashSet::new();
+    for (y, row) in rows.iter().enumerate() {
+        for (x, c) in row.iter().enumerate() {
+            let (dx, dy) = match c {
+                '>' => (1, 0),
+                '<' => (-1, 0),
+                'v' => (0, 1),
+                '^' => (0, -1),
+                _ => continue,
+            };
+            for t in 0..period {
+                let bx = (x as i64 + dx * t).rem_euclid(width);
+                let by = (y as i64 + dy * t).rem_euclid(height);
+                blocked.insert((bx, by, t));
+            }
+        }
+    }
+    let entrance = (0, -1);
+    let exit = (width - 1, height);
+    let free = |(x, y): (i64, i64), t: i64| (x, y) == entrance || (x, y) == exit
+        || (0..width).contains(&x) && (0..height).contains(&y) && !blocked.contains(&(x, y, t % period));
+
+    let mut time = 0;
+    for (from, to) in [(entrance, exit), (exit, entrance), (entrance, exit)] {
+        let mut seen: HashSet<((i64, i64), i64)> = HashSet::from([(from, time % period)]);
+        let mut queue = VecDeque::from([(from, time)]);
+        time = loop {
+            let Some((pos, t)) = queue.pop_front() else { return false };
+            if pos == to {
+                break t;
+            }
+            for (dx, dy) in [(0, 0), (1, 0), (-1, 0), (0, 1), (0, -1)] {
+                let next = (pos.0 + dx, pos.1 + dy);
+                if free(next, t + 1) && seen.insert((next, (t + 1) % period)) {
+                    queue.push_back((next, t + 1));
+                }
+            }
+        };
+    }
+    true
+}
+
+/// Random input: a valley `size` squares wide (at least 6) and a fifth as tall,
+/// with a blizzard in about a third of the squares. As in the real puzzle, no
+/// blizzard moves up or down the entrance and exit columns. Valleys that can't
+/// be crossed three times are rerolled.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let width = size.max(6);
+    let height = (width / 5).max(3);
+    loop {
+        let rows: Vec<Vec<char>> = (0..height)
+            .map(|_| (0..width)
+                .map(|x| match x {
+                    _ if !rng.chance(1, 3) => '.',
+                    _ if x == 0 || x == width - 1 => *rng.pick(&['<', '>']),
+                    _ => *rng.pick(&['<', '>', '^', 'v']),
+                })
+                .collect())
+            .collect();
+        if !crossable(&rows) {
+            continue;
+        }
+        let mut out = format!("#.{}\n", "#".repeat(width));
+        for row in rows {
+            out += &format!("#{}#\n", row.iter().collect::<String>());
+        }
+        out += &format!("{}.#\n", "#".repeat(width));
+        return out;
+    }
 }
 
 #[cfg(test)]
@@ -232,7 +330,7 @@ mod tests {
     #[test]
     fn day24_test() {
         let input: Vec<String> = test_input(include_str!("day24.testinput"));
-        let valley = Valley::from_input(input);
+        let valley = Valley::from_input(input).unwrap();
         assert_eq!(valley.search(1), 18);
         assert_eq!(valley.search(3), 54);
     }
@@ -2,10 +2,12 @@ use std::convert::From;
 use std::fmt;
 use std::str::FromStr;
 use std::vec::Vec;
-use ya_advent_lib::read::read_input;
+use crate::{AnswerKind, NoAnswer, ParseError, Solution};
+use crate::parse::lines;
+use crate::rng::Rng;
 
 #[derive(Debug, PartialEq, Copy, Clone)]
-struct Snafu(i64);
+pub struct Snafu(i64);
 
 impl Snafu {
     fn to_s( val: i64) -> String {
@@ -20,19 +22,24 @@ impl Snafu {
 }
 
 impl FromStr for Snafu {
-    type Err = ();
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseError::new(1, s, "a SNAFU number"));
+        }
         let mut result: i64 = 0;
-        for c in s.chars() {
-            result *= 5;
-            result += match c {
+        for (x, c) in s.chars().enumerate() {
+            let digit = match c {
                 '=' => -2,
                 '-' => -1,
                 '0' => 0,
                 '1' => 1,
                 '2' => 2,
-                _ => panic!(),
-            }
+                _ => return Err(ParseError::new(x + 1, c, "a SNAFU digit =, -, 0, 1 or 2")),
+            };
+            result = result.checked_mul(5)
+                .and_then(|r| r.checked_add(digit))
+                .ok_or_else(|| ParseError::new(1, s, "a SNAFU number that fits in 64 bits"))?;
         }
         Ok(Snafu(result))
     }
@@ -63,10 +70,35 @@ impl From<Snafu> for i64 {
     }
 }
 
-fn main() {
-    let input: Vec<Snafu> = read_input();
-    let sum = input.iter().map(|s| s.0).sum::<i64>();
-    println!("Part 1: {}", Snafu::from(sum));
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Input = Vec<Snafu>;
+    type Answer1 = Snafu;
+    type Answer2 = NoAnswer;
+
+    const DAY: u32 = 25;
+    const HAS_PART2: bool = false;
+    const KIND1: AnswerKind = AnswerKind::String;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        lines(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        Snafu::from(input.iter().map(|s| s.0).sum::<i64>())
+    }
+
+    fn part2(&self, _input: &Self::Input) -> Self::Answer2 {
+        NoAnswer
+    }
+}
+
+/// Random input with `size` SNAFU numbers.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| format!("{}\n", Snafu::from(rng.range(1..=5i64.pow(19)))))
+        .collect()
 }
 
 #[cfg(test)]
@@ -0,0 +1,117 @@
+use crate::Runner;
+use crate::check::Mismatch;
+use crate::rng::Rng;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+
+pub const ALL: [&dyn Runner; 25] = [
+    &day01::Day01, &day02::Day02, &day03::Day03, &day04::Day04, &day05::Day05,
+    &day06::Day06, &day07::Day07, &day08::Day08, &day09::Day09, &day10::Day10,
+    &day11::Day11, &day12::Day12, &day13::Day13, &day14::Day14, &day15::Day15::PUZZLE,
+    &day16::Day16, &day17::Day17, &day18::Day18, &day19::Day19, &day20::Day20,
+    &day21::Day21, &day22::Day22, &day23::Day23, &day24::Day24, &day25::Day25,
+];
+
+/// Looks up the solver for a day, numbered from 1.
+pub fn get(day: usize) -> Option<&'static dyn Runner> {
+    day.checked_sub(1).and_then(|idx| ALL.get(idx)).copied()
+}
+
+/// A day's random input generator, and a size that roughly matches the real
+/// puzzle input. What the size counts differs from day to day.
+#[derive(Clone, Copy)]
+pub struct Generator {
+    pub generate: fn(&mut Rng, usize) -> String,
+    pub default_size: usize,
+}
+
+const fn gen(generate: fn(&mut Rng, usize) -> String, default_size: usize) -> Generator {
+    Generator { generate, default_size }
+}
+
+pub const GENERATORS: [Generator; 25] = [
+    gen(day01::generate, 250), gen(day02::generate, 2500), gen(day03::generate, 300),
+    gen(day04::generate, 1000), gen(day05::generate, 500), gen(day06::generate, 4096),
+    gen(day07::generate, 180), gen(day08::generate, 99), gen(day09::generate, 2000),
+    gen(day10::generate, 140), gen(day11::generate, 8), gen(day12::generate, 160),
+    gen(day13::generate, 150), gen(day14::generate, 150), gen(day15::generate, 30),
+    gen(day16::generate, 60), gen(day17::generate, 10091), gen(day18::generate, 2800),
+    gen(day19::generate, 30), gen(day20::generate, 5000), gen(day21::generate, 2200),
+    gen(day22::generate, 2000), gen(day23::generate, 70), gen(day24::generate, 120),
+    gen(day25::generate, 120),
+];
+
+/// Looks up the input generator for a day, numbered from 1.
+pub fn generator(day: usize) -> Option<Generator> {
+    day.checked_sub(1).and_then(|idx| GENERATORS.get(idx)).copied()
+}
+
+/// Compares a day's solver with its brute-force reference on one small random input.
+pub type Checker = fn(&mut Rng) -> Result<(), Mismatch>;
+
+/// The days whose optimized solvers have a brute-force reference.
+pub const CHECKERS: [(usize, Checker); 4] = [
+    (15, day15::check), (16, day16::check), (17, day17::check), (19, day19::check),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AnswerKind;
+
+    #[test]
+    fn generators_test() {
+        for (idx, generator) in GENERATORS.iter().enumerate() {
+            // Day 15's part 2 always scans the full 4,000,000 rows.
+            if idx + 1 == 15 {
+                continue;
+            }
+            let input = (generator.generate)(&mut Rng::new(idx as u64), 10);
+            assert!(ALL[idx].run(&input).is_ok(), "day {}", idx + 1);
+        }
+    }
+
+    #[test]
+    fn answer_kinds_test() {
+        assert_eq!(get(1).unwrap().answer_kinds(), [AnswerKind::Number; 2]);
+        assert_eq!(get(10).unwrap().answer_kinds(), [AnswerKind::Number, AnswerKind::Art]);
+        // A SNAFU number made of 0s, 1s and 2s still isn't a number.
+        assert_eq!(get(25).unwrap().run("1=\n1-\n").unwrap().part1, "12");
+        assert_eq!(get(25).unwrap().answer_kinds()[0], AnswerKind::String);
+    }
+
+    #[test]
+    fn checkers_test() {
+        for (day, check) in CHECKERS {
+            for seed in 0..3 {
+                if let Err(mismatch) = check(&mut Rng::new(seed)) {
+                    panic!("day {day} seed {seed}: {mismatch:?}");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use std::fs;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+/// The directory searched for `dayNN.txt` when no input is given.
+pub const DEFAULT_DIR: &str = "inputs";
+
+/// Where to read a day's puzzle input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Path(PathBuf),
+    Stdin,
+    /// Stdin if anything comes on it, otherwise like `Dir`. Stdin that isn't
+    /// a terminal can still be empty, as under cron or `< /dev/null`.
+    StdinOr(PathBuf),
+    /// `dayNN.txt` in the given directory, falling back to the old
+    /// `dayNN.input` in the current directory.
+    Dir(PathBuf),
+}
+
+impl Default for Source {
+    fn default() -> Self {
+        Source::Dir(DEFAULT_DIR.into())
+    }
+}
+
+fn read_file(path: &Path) -> io::Result<String> {
+    fs::read_to_string(path)
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {e}", path.display())))
+}
+
+/// The file a `Dir` source reads for `day`.
+fn dir_path(day: usize, dir: &Path) -> PathBuf {
+    let path = dir.join(format!("day{day:02}.txt"));
+    let legacy = PathBuf::from(format!("day{day:02}.input"));
+    if !path.exists() && legacy.exists() {
+        legacy
+    }
+    else {
+        path
+    }
+}
+
+pub fn read(day: usize, source: &Source) -> io::Result<String> {
+    match source {
+        Source::Path(path) => read_file(path),
+        Source::Stdin => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            Ok(input)
+        },
+        Source::StdinOr(dir) => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            if input.is_empty() {
+                return read_file(&dir_path(day, dir));
+            }
+            Ok(input)
+        },
+        Source::Dir(dir) => read_file(&dir_path(day, dir)),
+    }
+}
+
+/// Like `read`, but hands back a reader instead of loading the whole input.
+pub fn open(day: usize, source: &Source) -> io::Result<Box<dyn BufRead>> {
+    let path = match source {
+        Source::Path(path) => path.clone(),
+        Source::Stdin => return Ok(Box::new(io::stdin().lock())),
+        Source::StdinOr(dir) => {
+            let mut stdin = io::stdin().lock();
+            if !stdin.fill_buf()?.is_empty() {
+                return Ok(Box::new(stdin));
+            }
+            dir_path(day, dir)
+        },
+        Source::Dir(dir) => dir_path(day, dir),
+    };
+    File::open(&path)
+        .map(|f| Box::new(BufReader::new(f)) as Box<dyn BufRead>)
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {e}", path.display())))
+}
@@ -0,0 +1,126 @@
+use std::fmt::{self, Display};
+use std::time::{Duration, Instant};
+
+pub mod answers;
+pub mod check;
+pub mod days;
+pub mod input;
+pub mod parse;
+pub mod rng;
+
+pub use parse::ParseError;
+
+/// A day's puzzle: how to parse its input and how to answer each part.
+pub trait Solution {
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    const DAY: u32;
+
+    /// False for puzzles with only one part; `part2` is never called for them.
+    const HAS_PART2: bool = true;
+
+    /// What sort of value each part's answer is, since a rendered answer like
+    /// day 25's `12` can't be told apart from a number.
+    const KIND1: AnswerKind = AnswerKind::Number;
+    const KIND2: AnswerKind = AnswerKind::Number;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(&self, input: &Self::Input) -> Self::Answer1;
+    fn part2(&self, input: &Self::Input) -> Self::Answer2;
+}
+
+/// Object-safe view of a `Solution`, so that all the days can live in one table.
+pub trait Runner: Sync {
+    fn day(&self) -> u32;
+    fn run_timed(&self, input: &str) -> Result<(Answers, Timings), ParseError>;
+    fn answer_kinds(&self) -> [AnswerKind; 2];
+
+    fn run(&self, input: &str) -> Result<Answers, ParseError> {
+        self.run_timed(input).map(|(answers, _)| answers)
+    }
+
+    /// Runs every stage `iterations` times, returning the time of each run.
+    fn bench(&self, input: &str, iterations: usize) -> Result<Vec<Timings>, ParseError> {
+        (0..iterations)
+            .map(|_| self.run_timed(input).map(|(_, timings)| timings))
+            .collect()
+    }
+}
+
+impl<S: Solution + Sync> Runner for S {
+    fn day(&self) -> u32 {
+        S::DAY
+    }
+
+    fn answer_kinds(&self) -> [AnswerKind; 2] {
+        [S::KIND1, S::KIND2]
+    }
+
+    fn run_timed(&self, input: &str) -> Result<(Answers, Timings), ParseError> {
+        let start = Instant::now();
+        let input = self.parse(input).map_err(|e| e.for_day(S::DAY))?;
+        let parse = start.elapsed();
+
+        let start = Instant::now();
+        let part1 = self.part1(&input);
+        let part1_time = start.elapsed();
+
+        let (part2, part2_time) = if S::HAS_PART2 {
+            let start = Instant::now();
+            let part2 = self.part2(&input);
+            let elapsed = start.elapsed();
+            (Some(part2.to_string()), Some(elapsed))
+        }
+        else {
+            (None, None)
+        };
+
+        let answers = Answers { part1: part1.to_string(), part2 };
+        Ok((answers, Timings { parse, part1: part1_time, part2: part2_time }))
+    }
+}
+
+/// Wall time spent in each stage of a run. Rendering the answers isn't counted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Option<Duration>,
+}
+
+/// The answers produced by one day's puzzle, already rendered for display.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answers {
+    pub part1: String,
+    pub part2: Option<String>,
+}
+
+/// What sort of value an answer is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnswerKind {
+    Number,
+    String,
+    /// A multi-line picture, like day 10's CRT output.
+    Art,
+}
+
+impl AnswerKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            AnswerKind::Number => "number",
+            AnswerKind::String => "string",
+            AnswerKind::Art => "art",
+        }
+    }
+}
+
+/// Stands in for the answer to a part that has no puzzle (day 25 part 2).
+pub struct NoAnswer;
+
+impl Display for NoAnswer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "-")
+    }
+}
@@ -0,0 +1,217 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use std::vec::Vec;
+use regex::{Captures, Regex};
+use ya_advent_lib::grid::Grid;
+
+/// Why and where a puzzle input failed to parse.
+///
+/// Line parsers only know the column; the line number is filled in by
+/// [`lines`] and friends, and the day by the runner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: Option<u32>,
+    /// 1-based; 0 until the error has been placed on a line.
+    pub line: usize,
+    /// 1-based, counted in characters.
+    pub column: usize,
+    pub text: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(column: usize, text: impl Into<String>, expected: impl Into<String>) -> Self {
+        Self {
+            day: None,
+            line: 0,
+            column,
+            text: text.into(),
+            expected: expected.into(),
+        }
+    }
+
+    pub fn at_line(self, line: usize) -> Self {
+        Self { line, ..self }
+    }
+
+    pub fn for_day(self, day: u32) -> Self {
+        Self { day: Some(day), ..self }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {day}, ")?;
+        }
+        if self.line > 0 {
+            write!(f, "line {}, ", self.line)?;
+        }
+        write!(f, "column {}: expected {}, ", self.column, self.expected)?;
+        if self.text.is_empty() {
+            write!(f, "found end of line")
+        }
+        else {
+            write!(f, "found {:?}", self.text)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses `text`, which starts at `column` of its line.
+pub fn parse_at<T: FromStr>(text: &str, column: usize, expected: &str) -> Result<T, ParseError> {
+    text.parse::<T>().map_err(|_| ParseError::new(column, text, expected))
+}
+
+/// A line matched against a regex. Fields are parsed with errors that point
+/// at the capture group they came from.
+pub struct Fields<'a> {
+    line: &'a str,
+    caps: Captures<'a>,
+}
+
+impl<'a> Fields<'a> {
+    pub fn str(&self, group: usize) -> &'a str {
+        self.caps.get(group).map_or("", |m| m.as_str())
+    }
+
+    /// The 1-based column of a capture group, or the end of the match if it
+    /// didn't participate.
+    pub fn column(&self, group: usize) -> usize {
+        let offset = match self.caps.get(group) {
+            Some(m) => m.start(),
+            None => self.caps.get(0).unwrap().end(),
+        };
+        self.line[..offset].chars().count() + 1
+    }
+
+    pub fn get<T: FromStr>(&self, group: usize, expected: &str) -> Result<T, ParseError> {
+        parse_at(self.str(group), self.column(group), expected)
+    }
+
+    /// An error pointing at a capture group whose text was well-formed but
+    /// not acceptable.
+    pub fn error(&self, group: usize, expected: &str) -> ParseError {
+        ParseError::new(self.column(group), self.str(group), expected)
+    }
+}
+
+pub fn try_match<'a>(re: &Regex, line: &'a str) -> Option<Fields<'a>> {
+    re.captures(line).map(|caps| Fields { line, caps })
+}
+
+/// Matches a line that has only one valid form, described by `expected`.
+pub fn match_line<'a>(re: &Regex, line: &'a str, expected: &str) -> Result<Fields<'a>, ParseError> {
+    try_match(re, line).ok_or_else(|| ParseError::new(1, line, expected))
+}
+
+fn numbered_lines(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    input
+        .trim_end_matches(['\n', '\r'])
+        .lines()
+        .map(|l| l.trim_end_matches('\r'))
+        .enumerate()
+        .map(|(idx, l)| (idx + 1, l))
+}
+
+pub fn lines_with<T, F>(input: &str, mut parse: F) -> Result<Vec<T>, ParseError>
+where F: FnMut(&str) -> Result<T, ParseError> {
+    numbered_lines(input)
+        .map(|(n, l)| parse(l).map_err(|e| e.at_line(n)))
+        .collect()
+}
+
+pub fn lines<T>(input: &str) -> Result<Vec<T>, ParseError>
+where T: FromStr<Err = ParseError> {
+    lines_with(input, str::parse)
+}
+
+/// Parses blank-line separated groups of lines.
+pub fn groups_with<T, F>(input: &str, mut parse: F) -> Result<Vec<Vec<T>>, ParseError>
+where F: FnMut(&str) -> Result<T, ParseError> {
+    let mut data: Vec<Vec<T>> = Vec::new();
+    let mut group: Vec<T> = Vec::new();
+    for (n, line) in numbered_lines(input) {
+        if line.is_empty() {
+            data.push(group);
+            group = Vec::new();
+        }
+        else {
+            group.push(parse(line).map_err(|e| e.at_line(n))?);
+        }
+    }
+    if !group.is_empty() {
+        data.push(group);
+    }
+    Ok(data)
+}
+
+pub fn groups<T>(input: &str) -> Result<Vec<Vec<T>>, ParseError>
+where T: FromStr<Err = ParseError> {
+    groups_with(input, str::parse)
+}
+
+/// Parses two sections of different kinds of line, separated by a blank line.
+pub fn sections<T1, T2>(input: &str) -> Result<(Vec<T1>, Vec<T2>), ParseError>
+where T1: FromStr<Err = ParseError>, T2: FromStr<Err = ParseError> {
+    let mut data1: Vec<T1> = Vec::new();
+    let mut data2: Vec<T2> = Vec::new();
+    let mut lines = numbered_lines(input);
+    for (n, line) in lines.by_ref() {
+        if line.is_empty() { break; }
+        data1.push(line.parse().map_err(|e: ParseError| e.at_line(n))?);
+    }
+    for (n, line) in lines {
+        data2.push(line.parse().map_err(|e: ParseError| e.at_line(n))?);
+    }
+    Ok((data1, data2))
+}
+
+/// Finds the first character in a block of lines that `parse` rejects.
+/// `first_line` is the line number of `lines[0]` within the whole input.
+pub fn check_chars<T, F>(lines: &[String], first_line: usize, parse: F) -> Result<(), ParseError>
+where F: Fn(char) -> Result<T, ParseError> {
+    for (y, line) in lines.iter().enumerate() {
+        for (x, c) in line.chars().enumerate() {
+            if let Err(e) = parse(c) {
+                return Err(ParseError { column: x + 1, ..e }.at_line(first_line + y));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Builds a grid from a block of lines, checking every character first since
+/// the `Grid` constructors can't say where a bad one was.
+pub fn grid<T>(lines: &[String], first_line: usize, default: T, padding: i64) -> Result<Grid<T>, ParseError>
+where T: Copy + TryFrom<char, Error = ParseError> {
+    check_chars(lines, first_line, T::try_from)?;
+    Grid::try_from_input(lines, default, padding)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_error_test() {
+        let re = Regex::new(r"^(\w) (\d+)$").unwrap();
+        let parse = |l: &str| -> Result<(char, u8), ParseError> {
+            let f = match_line(&re, l, "a direction and a distance")?;
+            Ok((f.get(1, "a direction")?, f.get(2, "a distance up to 255")?))
+        };
+        assert_eq!(lines_with("U 1\r\nD 2\n\n", parse), Ok(vec![('U', 1), ('D', 2)]));
+
+        let err = lines_with("U 1\nD 256\n", parse).unwrap_err().for_day(9);
+        assert_eq!(err.line, 2);
+        assert_eq!(err.column, 3);
+        assert_eq!(err.to_string(),
+            "day 9, line 2, column 3: expected a distance up to 255, found \"256\"");
+
+        let err = groups_with("U 1\n\nD\n", parse).unwrap_err();
+        assert_eq!(err.to_string(),
+            "line 3, column 1: expected a direction and a distance, found \"D\"");
+    }
+}
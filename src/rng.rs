@@ -0,0 +1,45 @@
+use std::ops::RangeInclusive;
+
+/// A small deterministic PRNG (SplitMix64), so that a generated input can be
+/// reproduced from its seed on any machine.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0);
+        (self.next_u64() % n as u64) as usize
+    }
+
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let span = (range.end() - range.start()) as u64 + 1;
+        range.start() + (self.next_u64() % span) as i64
+    }
+
+    /// True with probability `num / denom`.
+    pub fn chance(&mut self, num: usize, denom: usize) -> bool {
+        self.below(denom) < num
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}